@@ -0,0 +1,94 @@
+//! Command line front end, see `supreme_drafter_rs::cli`.
+
+use std::io::{self, Write};
+use std::process;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use supreme_drafter_rs::cli::{self, Command};
+use supreme_drafter_rs::draft::Draft;
+use supreme_drafter_rs::rng::SeededRng;
+use supreme_drafter_rs::{simulate, validate};
+
+fn main() {
+    if let Err(e) = run() {
+        eprintln!("supreme-drafter: {}", e);
+        process::exit(1);
+    }
+}
+
+fn run() -> Result<(), String> {
+    let args:Vec<String> = std::env::args().skip(1).collect();
+    // keep fresh seeds short enough to read out, as the page does
+    let seed = SystemTime::now().duration_since(UNIX_EPOCH).map(|time| time.as_nanos() as u32 as u64).unwrap_or(0);
+    let mut options = cli::parse_args(&args, seed).map_err(|e| format!("{}\n\n{}", e, cli::USAGE))?;
+    if options.command == Command::Help {
+        println!("{}", cli::USAGE);
+        return Ok(());
+    }
+    let mut pools = vec![];
+    for path in &options.pools {
+        let contents = std::fs::read_to_string(path).map_err(|e| format!("could not read {}: {}", path, e))?;
+        pools.push((String::from(cli::pool_file_name(path)), contents));
+    }
+    if options.command == Command::Validate {
+        let generator = supreme_drafter_rs::builtin_generator().map_err(|e| e.to_string())?;
+        let report = if pools.is_empty() {
+            generator.validate()
+        } else {
+            validate::validate_sets(&cli::load_pools(&pools)?, generator.templates())
+        };
+        print(&if options.json {serde_json::to_string_pretty(&report).unwrap_or_default()} else {report.to_text()})?;
+        if report.has_errors() {
+            process::exit(1);
+        }
+        return Ok(());
+    }
+    let (generator, pool_sets) = cli::load_generator(&pools)?;
+    if options.setup.sets.is_empty() {
+        options.setup.sets = cli::default_sets(&pool_sets);
+    }
+    if options.command == Command::Stats {
+        // every listed set is opened at least once per sample, whatever the draft's pack count
+        for (_, num_packs) in options.setup.sets.iter_mut() {
+            *num_packs = (*num_packs).max(1);
+        }
+        options.setup.format.num_packs = options.setup.sets.iter().map(|(_, num_packs)| num_packs).sum();
+    }
+    match options.command {
+        Command::Stats => {
+            let mut generator = generator;
+            let stats = simulate::simulate_packs(&mut generator, &options.setup.sets, options.samples, options.setup.format.pack_size, &mut SeededRng::new(options.setup.seed))
+                .map_err(|e| e.to_string())?;
+            print(&if options.json {
+                serde_json::to_string_pretty(&stats).unwrap_or_default()
+            } else {
+                simulate::stats_to_text(&stats, 5)
+            })
+        }
+        Command::Draft => {
+            let mut draft = Draft::new(generator, SeededRng::new(options.setup.seed));
+            draft.start_setup(&options.setup).map_err(|e| e.to_string())?;
+            println!("Seed {}", options.setup.seed);
+            let stdin = io::stdin();
+            cli::run_draft(&mut draft, &mut stdin.lock(), &mut io::stdout()).map_err(|e| e.to_string())
+        }
+        _ => {
+            let mut draft = Draft::new(generator, SeededRng::new(options.setup.seed));
+            draft.start_setup(&options.setup).map_err(|e| e.to_string())?;
+            let packs = cli::generate_packs(&mut draft).map_err(|e| e.to_string())?;
+            print(&if options.json {
+                cli::packs_to_json(&packs)
+            } else {
+                format!("Seed {}\n\n{}", options.setup.seed, cli::packs_to_text(&packs))
+            })
+        }
+    }
+}
+
+// written rather than printed, so piping into `head` ends quietly
+fn print(text:&str) -> Result<(), String> {
+    match writeln!(io::stdout(), "{}", text) {
+        Err(e) if e.kind() != io::ErrorKind::BrokenPipe => Err(e.to_string()),
+        _ => Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+//! Getting exported text out of the page, either onto the clipboard or into a downloaded file.
+
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use yew::Callback;
+
+/// Starts writing `text` to the clipboard and reports whether it worked once the browser answers.
+///
+/// The clipboard is missing outside secure contexts and writes can be refused,
+/// so failures come back through `done` rather than being dropped.
+pub fn copy_text(text:&str, done:Callback<Result<(), String>>) {
+    let clipboard = match web_sys::window() {
+        Some(window) => window.navigator().clipboard(),
+        None => return done.emit(Err(String::from("no browser window")))
+    };
+    if clipboard.is_undefined() {
+        return done.emit(Err(String::from("the clipboard is not available on this page")));
+    }
+    let promise = clipboard.write_text(text);
+    wasm_bindgen_futures::spawn_local(async move {
+        let result = JsFuture::from(promise).await
+            .map(|_| ())
+            .map_err(|e| e.as_string().unwrap_or_else(|| String::from("the browser refused the clipboard write")));
+        done.emit(result);
+    });
+}
+
+/// Saves `contents` as a file called `file_name` through a temporary object URL.
+pub fn download_text(file_name:&str, contents:&str) -> Result<(), JsValue> {
+    let document = web_sys::window().and_then(|window| window.document()).ok_or("no document")?;
+    let parts = js_sys::Array::of1(&JsValue::from_str(contents));
+    let options = web_sys::BlobPropertyBag::new();
+    options.set_type("text/plain");
+    let blob = web_sys::Blob::new_with_str_sequence_and_options(&parts, &options)?;
+    let url = web_sys::Url::create_object_url_with_blob(&blob)?;
+    let anchor:web_sys::HtmlAnchorElement = document.create_element("a")?.dyn_into()?;
+    anchor.set_href(&url);
+    anchor.set_download(file_name);
+    anchor.click();
+    web_sys::Url::revoke_object_url(&url)
+}
+
+/// Whether a key press belongs to a text field or select, which keeps its own keys.
+///
+/// Buttons stay focused after a click, so they are not counted; the shortcut
+/// handler's `prevent_default` stops Space and Enter from also clicking them.
+pub fn is_typing(event:&web_sys::KeyboardEvent) -> bool {
+    match event.target().and_then(|target| target.dyn_into::<web_sys::Element>().ok()) {
+        Some(element) => ["INPUT", "SELECT", "TEXTAREA"].contains(&element.tag_name().as_str()),
+        None => false
+    }
+}
+
+/// How many children of the element `id` share the first row, as the browser wrapped them.
+pub fn grid_columns(id:&str) -> usize {
+    let children = match web_sys::window().and_then(|window| window.document()).and_then(|document| document.get_element_by_id(id)) {
+        Some(grid) => grid.children(),
+        None => return 1
+    };
+    let top = |idx| children.item(idx).and_then(|child| child.dyn_into::<web_sys::HtmlElement>().ok()).map(|child| child.offset_top());
+    let first_row = top(0);
+    (0..children.length()).take_while(|idx| top(*idx) == first_row).count().max(1)
+}
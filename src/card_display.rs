@@ -7,13 +7,16 @@ pub struct CardDisplay {
     pub name: String,
     url: String,
     selected: bool,
+    wheeled: bool,
+    rating: Option<f64>,
+    focused: bool,
     class: String,
     onsignal: Callback<CardDisplay>,
 }
 
 impl std::cmp::PartialEq<CardDisplay> for CardDisplay {
     fn eq (&self, other:&CardDisplay) -> bool {
-        return self.name == *other.name;
+        self.name == *other.name
     }
 }
 
@@ -23,6 +26,13 @@ pub struct Props {
     pub name: String,
     pub url: String,
     pub selected: bool,
+    #[prop_or_default]
+    pub wheeled: bool,
+    #[prop_or_default]
+    pub rating: Option<f64>,
+    /// Highlighted for keyboard picking.
+    #[prop_or_default]
+    pub focused: bool,
     pub onsignal: Callback<CardDisplay>,
 }
 
@@ -31,7 +41,7 @@ pub enum Msg {
 }
 
 fn get_class_string (additional:String) -> String {
-    return format!("card-img-top card-block d-flex card-img {}", additional)
+    format!("card-img-top card-block d-flex card-img {}", additional)
 }
 
 impl CardDisplay {
@@ -57,11 +67,14 @@ impl Component for CardDisplay {
             name: props.name,
             url: props.url,
             selected: props.selected,
+            wheeled: props.wheeled,
+            rating: props.rating,
+            focused: props.focused,
             class: get_class_string(String::new()),
             onsignal: props.onsignal,
         };
         this.set_selected(this.selected);
-        return this
+        this
     }
 
     fn update(&mut self, msg: Self::Message) -> ShouldRender {
@@ -78,16 +91,25 @@ impl Component for CardDisplay {
         self.name = props.name;
         self.url = props.url;
         self.set_selected(props.selected);
+        self.wheeled = props.wheeled;
+        self.rating = props.rating;
+        self.focused = props.focused;
         self.onsignal = props.onsignal;
         true
     }
 
     fn view(&self) -> Html {
         html! {
-            <div class="card shadow-sm mx-1 px-1 pt-1 pb-1 mt-1">
+            <div class={format!("card shadow-sm mx-1 px-1 pt-1 pb-1 mt-1{}", if self.focused {" focused-card"} else {""})}>
                 <img class=&self.class src=self.url alt=self.name onclick=self.link.callback(|_| Msg::Clicked())/>
                 <div class="card-body align-items-center d-flex justify-content-center">
-                    <p class="card-text"><b>{&self.name}</b></p>
+                    <p class="card-text"><b>{&self.name}</b>
+                    {if self.wheeled {html!{<span class="badge badge-info ml-1">{"wheeled"}</span>}} else {html!{}}}
+                    {match self.rating {
+                        Some(rating) => html!{<span class="badge badge-secondary ml-1">{format!("{:.1}", rating)}</span>},
+                        None => html!{}
+                    }}
+                    </p>
                 </div>
             </div>
         }
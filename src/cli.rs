@@ -0,0 +1,241 @@
+//! The `supreme-drafter` command line, which prints generated packs or runs a
+//! draft in the terminal.
+//!
+//! Drafts are started with `Draft::start_setup` on the bundled sets from
+//! `builtin_generator`, exactly as the page does, so a seed and setup give the
+//! same packs in both. The binary itself only reads files and the terminal.
+
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::custom_sets::{clashing_sets, parse_pool_file};
+use crate::draft::{Card, Draft, DraftFormat, Pack, SetGenerator, SetupInfo};
+use crate::error::DraftError;
+use crate::export::{export_deck, ExportFormat};
+use crate::history::PickRecord;
+use crate::strategy::StrategyKind;
+use crate::undo::DEFAULT_UNDO_LIMIT;
+use crate::validate::validate_sets;
+
+pub const USAGE:&str = "\
+Usage: supreme-drafter [packs|draft|stats|validate] [options]
+
+Commands:
+  packs             print every pack of the draft (the default)
+  draft             draft in the terminal, picking cards by name or number
+  stats             open many packs of each set and report how they were collated
+  validate          check the --pool files (or the bundled sets) for data problems
+
+Options:
+  --set NAME:COUNT  open COUNT packs of set NAME; repeat for more sets
+  --seed N          seed for the draft, random if left out
+  --packs N         packs in the draft
+  --picks N         cards taken from each pack
+  --size N          cards per pack, instead of the set's usual size
+  --seats N         drafters at the table, counting you
+  --bots CODES      bot pick strategies in seat order, e.g. random,color
+  --pool FILE       add the sets in a pool file (set JSON or a cube list)
+  --samples N       times stats opens the pack series (default 1000)
+  --json            print packs, stats or the validation report as JSON
+  -h, --help        show this help
+
+Without --set, every set from --pool files (or MH1) fills the packs at random.";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Command {
+    Packs,
+    Draft,
+    Stats,
+    Validate,
+    Help
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CliOptions {
+    pub command:Command,
+    pub setup:SetupInfo,
+    /// Pool files to read, in the order given.
+    pub pools:Vec<String>,
+    pub samples:usize,
+    pub json:bool
+}
+
+/// Pack series opened by `stats` unless `--samples` says otherwise.
+pub const DEFAULT_SAMPLES:usize = 1000;
+
+/// Parses the arguments after the program name; `seed` is used when none is given.
+///
+/// The setup's sets are left empty when there is no `--set`, see `default_sets`.
+pub fn parse_args(args:&[String], seed:u64) -> Result<CliOptions, String> {
+    let mut command = Command::Packs;
+    let mut setup = SetupInfo{sets:vec![], seed, format:DraftFormat::default(), bot_strategies:vec![], undo_limit:DEFAULT_UNDO_LIMIT, pick_timer:None};
+    let mut pools = vec![];
+    let mut samples = DEFAULT_SAMPLES;
+    let mut json = false;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = |name:&str| args.next().cloned().ok_or_else(|| format!("{} needs a value", name));
+        match arg.as_str() {
+            "packs" => command = Command::Packs,
+            "draft" => command = Command::Draft,
+            "stats" => command = Command::Stats,
+            "validate" => command = Command::Validate,
+            "-h" | "--help" => command = Command::Help,
+            "--set" => {
+                let entry = value(arg)?;
+                let mut split = entry.splitn(2, ':');
+                let set = split.next().unwrap_or("").to_string();
+                let num_packs = split.next().and_then(|num_packs| num_packs.parse().ok()).filter(|num_packs| *num_packs >= 0);
+                match num_packs {
+                    Some(num_packs) if !set.is_empty() => setup.sets.push((set, num_packs)),
+                    _ => return Err(format!("--set takes NAME:COUNT, not {}", entry))
+                }
+            }
+            "--seed" => {
+                let seed = value(arg)?;
+                setup.seed = seed.parse().map_err(|_| format!("--seed takes a number, not {}", seed))?;
+            }
+            "--packs" => setup.format.num_packs = parse_number(arg, &value(arg)?)?,
+            "--picks" => setup.format.picks_per_pack = parse_number(arg, &value(arg)?)?,
+            "--size" => setup.format.pack_size = Some(parse_number(arg, &value(arg)?)?),
+            "--seats" => setup.format.seats = parse_number(arg, &value(arg)?)?,
+            "--bots" => {
+                let codes = value(arg)?;
+                setup.bot_strategies = codes.split(',').map(|code| StrategyKind::from_code(code).ok_or_else(|| format!("unknown bot strategy {}", code))).collect::<Result<_, _>>()?;
+            }
+            "--pool" => pools.push(value(arg)?),
+            "--samples" => samples = parse_number(arg, &value(arg)?)?,
+            "--json" => json = true,
+            _ => return Err(format!("unknown argument {}", arg))
+        }
+    }
+    Ok(CliOptions{command, setup, pools, samples, json})
+}
+
+fn parse_number<T:std::str::FromStr + Default + PartialOrd>(name:&str, value:&str) -> Result<T, String> {
+    match value.parse() {
+        Ok(number) if number > T::default() => Ok(number),
+        _ => Err(format!("{} takes a positive number, not {}", name, value))
+    }
+}
+
+/// The file name of a `--pool` path, which names its set; the directories leading to it are dropped.
+pub fn pool_file_name(path:&str) -> &str {
+    Path::new(path).file_name().and_then(|name| name.to_str()).unwrap_or(path)
+}
+
+/// The sets of every `(file name, contents)` pool file.
+pub fn load_pools(pools:&[(String, String)]) -> Result<Map<String, Value>, String> {
+    let mut pool_sets = Map::new();
+    for (file_name, contents) in pools {
+        pool_sets.extend(parse_pool_file(file_name, contents)?);
+    }
+    Ok(pool_sets)
+}
+
+/// The bundled sets plus the sets of every pool file, refusing pool files whose sets could not be drafted.
+pub fn load_generator(pools:&[(String, String)]) -> Result<(SetGenerator, Map<String, Value>), String> {
+    let mut generator = crate::builtin_generator().map_err(|e| e.to_string())?;
+    let pool_sets = load_pools(pools)?;
+    let clashes = clashing_sets(&pool_sets, &generator.set_names(), &Map::new());
+    if !clashes.is_empty() {
+        return Err(format!("the pool files reuse built-in set names, rename them: {}", clashes.join(", ")));
+    }
+    let report = validate_sets(&pool_sets, generator.templates());
+    if report.has_errors() {
+        return Err(format!("the pool files have errors, see `supreme-drafter validate`:\n{}", report.to_text()));
+    }
+    generator.add_sets(pool_sets.clone());
+    Ok((generator, pool_sets))
+}
+
+/// Sets to draw every pack from at random when no `--set` is given.
+pub fn default_sets(pool_sets:&Map<String, Value>) -> Vec<(String, i16)> {
+    if pool_sets.is_empty() {
+        vec![(String::from("MH1"), 0)]
+    } else {
+        pool_sets.keys().map(|set| (set.clone(), 0)).collect()
+    }
+}
+
+#[derive(Serialize)]
+struct PrintedPack<'a> {
+    pack_number:i16,
+    set:&'a str,
+    cards:&'a [Card]
+}
+
+/// Every pack the human is shown, found by always taking the first cards.
+///
+/// Picking this way uses no randomness, so when drafting alone these are the
+/// packs the draft opens whatever is picked; in a pod, later packs depend on the picks.
+pub fn generate_packs(draft:&mut Draft) -> Result<Vec<PickRecord>, DraftError> {
+    while !draft.is_finished() {
+        let names:Vec<String> = draft.current_pack().cards.iter().take(draft.picks_required()).map(|card| card.name.clone()).collect();
+        draft.pick(&names)?;
+    }
+    Ok(draft.history().to_vec())
+}
+
+pub fn packs_to_json(packs:&[PickRecord]) -> String {
+    let printed:Vec<PrintedPack> = packs.iter().map(|record| PrintedPack{pack_number:record.pack_number, set:&record.set, cards:&record.contents}).collect();
+    serde_json::to_string_pretty(&printed).unwrap_or_default()
+}
+
+pub fn packs_to_text(packs:&[PickRecord]) -> String {
+    packs.iter().map(|record| format!("Pack {} ({})\n{}", record.pack_number + 1, record.set, pack_lines(&record.contents))).collect::<Vec<_>>().join("\n")
+}
+
+fn pack_lines(cards:&[Card]) -> String {
+    cards.iter().enumerate().map(|(idx, card)| {
+        let mana_cost = card.mana_cost.as_ref().map(|mana_cost| format!(" {}", mana_cost)).unwrap_or_default();
+        format!("{:>3}. {}{} [{}]\n", idx + 1, card.name, mana_cost, card.rarity)
+    }).collect()
+}
+
+/// Reads a pick typed as card names or 1-based numbers separated by commas.
+pub fn parse_pick(input:&str, pack:&Pack, required:usize) -> Result<Vec<String>, String> {
+    let mut names:Vec<String> = vec![];
+    for token in input.split(',').map(str::trim).filter(|token| !token.is_empty()) {
+        let card = match token.parse::<usize>() {
+            Ok(number) => pack.cards.get(number.wrapping_sub(1)).ok_or_else(|| format!("there is no card {}", number))?,
+            Err(_) => pack.cards.iter().find(|card| card.name.eq_ignore_ascii_case(token)).ok_or_else(|| format!("{} is not in the pack", token))?
+        };
+        if names.contains(&card.name) {
+            return Err(format!("{} was picked twice", card.name));
+        }
+        names.push(card.name.clone());
+    }
+    if names.len() != required {
+        return Err(format!("pick {} card{}", required, if required == 1 {""} else {"s"}));
+    }
+    Ok(names)
+}
+
+/// Drafts by reading picks from `input` until the draft is over, then prints the picks as an Arena deck.
+///
+/// Typing `q` or closing the input stops early.
+pub fn run_draft(draft:&mut Draft, input:&mut dyn BufRead, output:&mut dyn Write) -> io::Result<()> {
+    while !draft.is_finished() {
+        let required = draft.picks_required();
+        writeln!(output, "\nPack {} of {}", draft.pack_number() + 1, draft.format().num_packs)?;
+        write!(output, "{}", pack_lines(&draft.current_pack().cards))?;
+        for short in &draft.current_pack().short_slots {
+            writeln!(output, "Short pack: {}", short)?;
+        }
+        write!(output, "Pick {} (names or numbers, comma separated): ", required)?;
+        output.flush()?;
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 || line.trim() == "q" {
+            break;
+        }
+        match parse_pick(&line, draft.current_pack(), required) {
+            Ok(names) => draft.pick(&names).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+            Err(e) => writeln!(output, "{}", e)?
+        }
+    }
+    writeln!(output, "\n{}", export_deck(ExportFormat::Arena, "", draft.picks(), &[]))
+}
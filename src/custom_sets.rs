@@ -0,0 +1,104 @@
+//! Reading user supplied card pools, such as a playgroup's cube, into the set
+//! JSON schema used by `small-sets.json` so they can be drafted like any set.
+//!
+//! Two kinds of file are understood:
+//!
+//! * JSON in the `small-sets.json` schema, either a whole map of sets or a
+//!   single set's map of cards (which is then named after the file)
+//! * a plain text list with one card name per line, optionally followed by
+//!   `|` separated fields: `m`, `r`, `u` or `c` for its rarity (common
+//!   otherwise), and a mana cost like `{1}{R}` or a mana value like `2`
+//!   (0 otherwise); blank lines and lines starting with `#` or `//` are skipped
+//!
+//! Uploaded sets may not reuse the name of a bundled set, see `clashing_sets`.
+
+use serde_json::{json, Value, Map};
+
+use crate::draft::mana_value;
+
+/// Parses an uploaded pool file into sets keyed by set name.
+pub fn parse_pool_file(file_name:&str, contents:&str) -> Result<Map<String, Value>, String> {
+    let set_name = set_name_from_file(file_name);
+    if contents.trim_start().starts_with('{') {
+        let value:Value = serde_json::from_str(contents).map_err(|e| format!("{} is not valid JSON: {}", file_name, e))?;
+        let object = value.as_object().ok_or_else(|| format!("{} does not contain a JSON object", file_name))?;
+        if object.values().all(is_card_entry) {
+            let mut sets = Map::new();
+            sets.insert(set_name, value);
+            return Ok(sets);
+        }
+        for (name, set) in object {
+            if !set.as_object().is_some_and(|cards| cards.values().all(is_card_entry)) {
+                return Err(format!("{} in {} is not a map of cards", name, file_name));
+            }
+        }
+        Ok(object.clone())
+    } else {
+        let mut sets = Map::new();
+        sets.insert(set_name, Value::Object(parse_cube_list(contents)?));
+        Ok(sets)
+    }
+}
+
+/// Parses a plain text list into a single set's map of cards.
+pub fn parse_cube_list(contents:&str) -> Result<Map<String, Value>, String> {
+    let mut cards = Map::new();
+    for (line_idx, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with("//") {
+            continue;
+        }
+        let mut fields = line.split('|').map(str::trim);
+        let name = fields.next().unwrap_or("");
+        if name.is_empty() {
+            return Err(format!("line {}: missing card name", line_idx + 1));
+        }
+        let mut card = json!({"c":0, "r":"c"});
+        for field in fields {
+            if let Some(rarity) = parse_rarity(field) {
+                card["r"] = json!(rarity);
+            } else if field.starts_with('{') {
+                card["c"] = json!(mana_value(field));
+                card["mc"] = json!(field);
+            } else if let Ok(cmc) = field.parse::<u16>() {
+                card["c"] = json!(cmc);
+            } else {
+                return Err(format!("line {}: {} is not a rarity, mana cost or mana value", line_idx + 1, field));
+            }
+        }
+        cards.insert(String::from(name), card);
+    }
+    if cards.is_empty() {
+        return Err(String::from("the list has no cards"));
+    }
+    Ok(cards)
+}
+
+/// Uploaded set names already taken by one of `existing_sets` that is not itself an upload.
+///
+/// Adding such a set would silently replace the bundled one, so uploads are refused until renamed.
+pub fn clashing_sets(sets:&Map<String, Value>, existing_sets:&[String], uploaded_sets:&Map<String, Value>) -> Vec<String> {
+    sets.keys().filter(|set| existing_sets.contains(set) && !uploaded_sets.contains_key(*set)).cloned().collect()
+}
+
+fn parse_rarity(rarity:&str) -> Option<&'static str> {
+    match rarity.to_lowercase().as_str() {
+        "m" | "mythic" => Some("m"),
+        "r" | "rare" => Some("r"),
+        "u" | "uncommon" => Some("u"),
+        "c" | "common" => Some("c"),
+        _ => None
+    }
+}
+
+fn is_card_entry(value:&Value) -> bool {
+    value.get("r").is_some_and(Value::is_string)
+}
+
+fn set_name_from_file(file_name:&str) -> String {
+    let stem = match file_name.rfind('.') {
+        Some(dot) if dot > 0 => &file_name[..dot],
+        _ => file_name
+    };
+    String::from(stem)
+}
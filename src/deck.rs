@@ -0,0 +1,91 @@
+//! Building a deck from the finished draft's picks.
+//!
+//! Every pick starts in the main deck and can be moved to the sideboard and
+//! back. Basic lands come from the land station in any quantity and are kept
+//! apart from the picks, so basics opened in packs are counted as picks.
+
+use std::collections::BTreeMap;
+
+use serde::{Serialize, Deserialize};
+
+use crate::draft::{get_named_img_url, Card};
+
+/// The basic lands offered by the land station, with the color each one makes.
+pub const BASIC_LANDS:[(&str, char); 5] = [("Plains", 'W'), ("Island", 'U'), ("Swamp", 'B'), ("Mountain", 'R'), ("Forest", 'G')];
+
+/// The usual minimum main deck size for limited.
+pub const MIN_DECK_SIZE:usize = 40;
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Deck {
+    pub main:Vec<Card>,
+    pub sideboard:Vec<Card>,
+    /// Land station basics by name.
+    pub basic_lands:BTreeMap<String, u16>
+}
+
+impl Deck {
+    /// A deck with every pick in the main deck and no basics added.
+    pub fn from_picks(picks:Vec<Card>) -> Self {
+        Deck{main:picks, ..Deck::default()}
+    }
+
+    /// Moves one copy of the named card to the sideboard, returning whether there was one to move.
+    pub fn move_to_sideboard(&mut self, name:&str) -> bool {
+        move_card(&mut self.main, &mut self.sideboard, name)
+    }
+
+    pub fn move_to_main(&mut self, name:&str) -> bool {
+        move_card(&mut self.sideboard, &mut self.main, name)
+    }
+
+    pub fn basic_land_count(&self, name:&str) -> u16 {
+        self.basic_lands.get(name).cloned().unwrap_or(0)
+    }
+
+    pub fn set_basic_land_count(&mut self, name:&str, count:u16) {
+        if count == 0 {
+            self.basic_lands.remove(name);
+        } else {
+            self.basic_lands.insert(String::from(name), count);
+        }
+    }
+
+    /// Cards in the main deck, counting land station basics.
+    pub fn main_size(&self) -> usize {
+        self.main.len() + self.basic_lands.values().map(|count| *count as usize).sum::<usize>()
+    }
+
+    /// The main deck with land station basics added as cards, e.g. for exporting.
+    pub fn main_with_lands(&self) -> Vec<Card> {
+        let mut cards = self.main.clone();
+        for (name, count) in &self.basic_lands {
+            let land = basic_land(name);
+            cards.extend(std::iter::repeat_n(land, *count as usize));
+        }
+        cards
+    }
+}
+
+fn move_card(from:&mut Vec<Card>, to:&mut Vec<Card>, name:&str) -> bool {
+    match from.iter().position(|card| card.name == name) {
+        Some(idx) => {
+            to.push(from.remove(idx));
+            true
+        }
+        None => false
+    }
+}
+
+/// A basic land as a card; `name` need not be one of `BASIC_LANDS`.
+pub fn basic_land(name:&str) -> Card {
+    let colors = BASIC_LANDS.iter().find(|(land, _)| *land == name).map(|(_, color)| color.to_string()).unwrap_or_default();
+    Card{
+        name:String::from(name),
+        img_url:get_named_img_url(name),
+        type_line:Some(format!("Basic Land — {}", name)),
+        colors,
+        rarity:'c',
+        ..Card::default()
+    }
+}
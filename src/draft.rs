@@ -0,0 +1,819 @@
+//! The draft engine: set data, pack collation and pick bookkeeping.
+//!
+//! Nothing in here touches yew or the browser, so the same code runs in the
+//! web app, in native tools and under `cargo test`.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::{Serialize, Deserialize};
+use serde_json::{Value, Map};
+
+use crate::error::DraftError;
+use crate::history::PickRecord;
+use crate::pod::{PassDirection, Pod, Seat};
+use crate::ratings::SetRatings;
+use crate::rng::{DraftRng, SeededRng, shuffle};
+use crate::strategy::{PickContext, PickStrategy, StrategyKind};
+use crate::template::{PackSlot, PackTemplate, RARITY_ORDER};
+use crate::validate::{validate_sets, Issue, IssueKind, Severity, ValidationReport};
+use crate::undo::DEFAULT_UNDO_LIMIT;
+
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Card {
+    pub cmc:i16,
+    pub name:String,
+    pub img_url:String,
+    /// Color identity as WUBRG letters in that order, empty for colorless.
+    #[serde(default)]
+    pub colors:String,
+    /// Mana cost in the usual `{2}{G}{G}` notation.
+    #[serde(default)]
+    pub mana_cost:Option<String>,
+    #[serde(default)]
+    pub type_line:Option<String>,
+    #[serde(default)]
+    pub power:Option<String>,
+    #[serde(default)]
+    pub toughness:Option<String>,
+    #[serde(default)]
+    pub text:Option<String>,
+    /// `m`, `r`, `u` or `c` as given by the set data.
+    #[serde(default)]
+    pub rarity:char,
+    /// Code of the set the card was opened from, e.g. `MH1`.
+    #[serde(default)]
+    pub set:String,
+    /// MTGO catalog id, which `.dek` exports need for MTGO to find the card.
+    #[serde(default)]
+    pub mtgo_id:Option<i64>
+}
+
+impl Card {
+    /// Whether `card_type` is one of the words of the type line, e.g. `Creature` or `Elf`.
+    pub fn has_type(&self, card_type:&str) -> bool {
+        match &self.type_line {
+            Some(type_line) => type_line.split_whitespace().any(|word| word == card_type),
+            None => false
+        }
+    }
+
+    pub fn is_creature(&self) -> bool {
+        self.has_type("Creature")
+    }
+
+    pub fn is_land(&self) -> bool {
+        self.has_type("Land")
+    }
+
+    /// Whether the set data gave the card's colors, cost or type, which the bundled sets do not.
+    pub fn has_details(&self) -> bool {
+        !self.colors.is_empty() || self.mana_cost.is_some() || self.type_line.is_some()
+    }
+}
+
+/// WUBRG letters appearing in a mana cost, in WUBRG order.
+pub fn colors_from_mana_cost(mana_cost:&str) -> String {
+    "WUBRG".chars().filter(|color| mana_cost.contains(*color)).collect()
+}
+
+/// Mana value of a cost like `{2}{G}{G}`: numbers count as themselves, `X` as
+/// nothing, hybrid symbols such as `{2/W}` as their larger half, and any other
+/// symbol as one.
+pub fn mana_value(mana_cost:&str) -> i16 {
+    mana_cost.split('{').skip(1).filter_map(|symbol| symbol.split('}').next()).map(|symbol| {
+        match symbol {
+            "X" | "Y" | "Z" => 0,
+            _ => symbol.split('/').map(|half| half.parse().unwrap_or(1)).max().unwrap_or(1)
+        }
+    }).sum()
+}
+
+impl std::cmp::PartialEq<Card> for Card {
+    fn eq (&self, other:&Card) -> bool {
+        self.name == other.name
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Pack {
+    pub cards:Vec<Card>,
+    pub num_picks:i16,
+    /// In a pod, the cards left when the human last passed this pack on.
+    #[serde(default)]
+    pub seen:Vec<String>,
+    /// Slots that ran out of different cards when the pack was opened.
+    #[serde(default)]
+    pub short_slots:Vec<ShortSlot>
+}
+
+impl Pack {
+    pub fn is_short(&self) -> bool {
+        !self.short_slots.is_empty()
+    }
+}
+
+/// A slot that may not repeat cards but needed more than its pools hold.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ShortSlot {
+    pub slot:String,
+    /// Cards taken from the nearest rarity outside the slot that still had some.
+    pub borrowed:u16,
+    /// Cards already in the pack, drawn again once every rarity was used up.
+    pub repeated:u16
+}
+
+impl fmt::Display for ShortSlot {
+    fn fmt(&self, f:&mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut makeup = vec![];
+        if self.borrowed > 0 {
+            makeup.push(format!("{} from another rarity", self.borrowed));
+        }
+        if self.repeated > 0 {
+            makeup.push(format!("{} repeated", self.repeated));
+        }
+        write!(f, "the {} slot ran out of cards ({})", self.slot, makeup.join(", "))
+    }
+}
+
+/// How many packs a draft has and how many cards are taken from each.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DraftFormat {
+    pub num_packs:i16,
+    pub picks_per_pack:i16,
+    /// Cards per pack, or `None` for whatever the set's pack template holds.
+    pub pack_size:Option<u16>,
+    /// Drafters at the table. With one seat each pick opens a fresh pack;
+    /// with more, packs are passed around bots until they run out.
+    #[serde(default = "default_seats")]
+    pub seats:u8
+}
+
+fn default_seats() -> u8 {
+    1
+}
+
+impl DraftFormat {
+    pub const SUPREME:DraftFormat = DraftFormat{num_packs:18, picks_per_pack:2, pack_size:None, seats:1};
+    pub const CLASSIC:DraftFormat = DraftFormat{num_packs:3, picks_per_pack:1, pack_size:None, seats:8};
+    pub const PICK_THREE:DraftFormat = DraftFormat{num_packs:12, picks_per_pack:3, pack_size:None, seats:1};
+
+    pub fn presets() -> Vec<(&'static str, DraftFormat)> {
+        vec![
+            ("Supreme (18 packs, pick 2)", DraftFormat::SUPREME),
+            ("Classic pod (8 seats, 3 packs, pick 1)", DraftFormat::CLASSIC),
+            ("Pick three (12 packs, pick 3)", DraftFormat::PICK_THREE)
+        ]
+    }
+
+    /// Refuses formats with no packs, no picks or empty packs, which would leave nothing to draft.
+    pub fn check(&self) -> Result<(), DraftError> {
+        if self.num_packs <= 0 {
+            return Err(DraftError::BadFormat(format!("it needs at least one pack, not {}", self.num_packs)));
+        }
+        if self.picks_per_pack <= 0 {
+            return Err(DraftError::BadFormat(format!("each pack needs at least one pick, not {}", self.picks_per_pack)));
+        }
+        if self.pack_size == Some(0) {
+            return Err(DraftError::BadFormat(String::from("packs need at least one card")));
+        }
+        Ok(())
+    }
+
+    /// Picks the human makes over the whole draft, when drafting alone.
+    pub fn total_picks(&self) -> i16 {
+        self.num_packs * self.picks_per_pack
+    }
+
+    pub fn is_pod(&self) -> bool {
+        self.seats > 1
+    }
+}
+
+impl Default for DraftFormat {
+    fn default() -> Self {
+        DraftFormat::SUPREME
+    }
+}
+
+/// What the drafter chose on the setup screen: packs per set, the format and the seed.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SetupInfo {
+    pub sets:Vec<(String, i16)>,
+    pub seed:u64,
+    #[serde(default)]
+    pub format:DraftFormat,
+    /// Pick strategy of each bot in seat order, the default for any not listed.
+    #[serde(default)]
+    pub bot_strategies:Vec<StrategyKind>,
+    /// How many picks can be undone; 0 turns undo off.
+    #[serde(default = "default_undo_limit")]
+    pub undo_limit:u16,
+    /// Seconds allowed for each pack before the pick is made automatically, or `None` for no timer.
+    #[serde(default)]
+    pub pick_timer:Option<u16>
+}
+
+fn default_undo_limit() -> u16 {
+    DEFAULT_UNDO_LIMIT
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct SetGenerator {
+    // far too big to save with a draft, see `Draft::resume`
+    #[serde(skip)]
+    all_set_json:Map<String, Value>,
+    mythics:Vec<Card>,
+    rares:Vec<Card>,
+    uncommons:Vec<Card>,
+    commons:Vec<Card>,
+    basics:Vec<Card>,
+    // static data like `all_set_json`
+    #[serde(skip)]
+    templates:BTreeMap<String, PackTemplate>,
+    #[serde(skip)]
+    default_template:PackTemplate,
+    pub pack_number:i16,
+    pub pack_series:Vec<String>
+}
+
+pub fn get_img_url (multiverse_id:i64) -> String {
+    format!("https://gatherer.wizards.com/Handlers/Image.ashx?multiverseid={}&type=card", multiverse_id)
+}
+
+/// Image looked up by card name, for custom pools that carry no multiverse ids.
+pub fn get_named_img_url (card_name:&str) -> String {
+    format!("https://api.scryfall.com/cards/named?exact={}&format=image", percent_encode(card_name))
+}
+
+/// Percent-encodes every byte but ASCII letters, digits and `-_.~`, for use in URLs.
+pub fn percent_encode(text:&str) -> String {
+    let mut encoded = String::new();
+    for byte in text.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => encoded.push(byte as char),
+            _ => encoded.push_str(&format!("%{:02X}", byte))
+        }
+    }
+    encoded
+}
+
+/// Basic lands fill their own slot rather than their rarity's.
+pub fn is_basic_land(card_name:&str) -> bool {
+    matches!(card_name,
+        "Forest" | "Mountain" | "Swamp" | "Plains" | "Island" |
+        "Snow-Covered Forest" | "Snow-Covered Mountain" | "Snow-Covered Swamp" | "Snow-Covered Plains" | "Snow-Covered Island")
+}
+
+fn invalid_set(set:&str, card:Option<&str>, kind:IssueKind) -> DraftError {
+    DraftError::InvalidSet(Issue{severity:Severity::Error, set:String::from(set), card:card.map(String::from), kind})
+}
+
+/// Number of packs left over for random sets once the setup's packs are counted.
+pub fn num_random_packs (setup_list:&[(String, i16)], num_packs:i16) -> i16 {
+    num_packs - setup_list.iter().map(|setup| setup.1).sum::<i16>()
+}
+
+impl SetGenerator {
+    // a copy of the pools and pack series only, see `Draft::snapshot`
+    fn clone_state(&self) -> Self {
+        SetGenerator{
+            all_set_json:Map::new(),
+            mythics:self.mythics.clone(),
+            rares:self.rares.clone(),
+            uncommons:self.uncommons.clone(),
+            commons:self.commons.clone(),
+            basics:self.basics.clone(),
+            templates:BTreeMap::new(),
+            default_template:self.default_template.clone(),
+            pack_number:self.pack_number,
+            pack_series:self.pack_series.clone()
+        }
+    }
+
+    /// Takes set data in the `small-sets.json` schema, a map of set names to maps of cards.
+    pub fn new (all_set_json:Value) -> Result<Self, DraftError> {
+        match all_set_json {
+            Value::Object(all_set_json) => Ok(SetGenerator{all_set_json, ..SetGenerator::default()}),
+            _ => Err(DraftError::BadData(String::from("the set data is not a map of sets")))
+        }
+    }
+
+    pub fn set_names(&self) -> Vec<String> {
+        self.all_set_json.keys().cloned().collect()
+    }
+
+    /// Sets the per-set collation overrides; sets without one use `PackTemplate::default()`.
+    pub fn set_templates(&mut self, templates:BTreeMap<String, PackTemplate>) {
+        self.templates = templates;
+    }
+
+    /// Registers extra sets, replacing any existing set with the same name.
+    pub fn add_sets(&mut self, sets:Map<String, Value>) {
+        self.all_set_json.extend(sets);
+    }
+
+    pub fn is_basic(&self, card_name:&str) -> bool {
+        is_basic_land(card_name)
+    }
+
+    pub fn templates(&self) -> &BTreeMap<String, PackTemplate> {
+        &self.templates
+    }
+
+    /// Checks every loaded set, see `validate::validate_sets`.
+    pub fn validate(&self) -> ValidationReport {
+        validate_sets(&self.all_set_json, &self.templates)
+    }
+    pub fn init_from_setup_packs (&mut self, setup_list:&[(String, i16)], num_random_packs:i16, rng:&mut dyn DraftRng) -> Result<(), DraftError> {
+        if setup_list.is_empty() {
+            return Err(DraftError::NoSets);
+        }
+        // every set is checked up front, so bad data stops the draft from starting rather than failing a later pick
+        let mut used_sets = Map::new();
+        for (set, _) in setup_list {
+            let cards = self.all_set_json.get(set).ok_or_else(|| DraftError::UnknownSet(set.clone()))?;
+            used_sets.insert(set.clone(), cards.clone());
+        }
+        if let Some(issue) = validate_sets(&used_sets, &self.templates).errors().next() {
+            return Err(DraftError::InvalidSet(issue.clone()));
+        }
+        let mut set_list = vec![];
+        // track one of each set for usage in making the random sets
+        let mut all_set_selection_list = vec![];
+        for setup in setup_list {
+            all_set_selection_list.push(setup.0.clone());
+            for _ in 0..setup.1 {
+                set_list.push(setup.0.clone());
+            }
+        }
+        // This insertion could of course be a binary search instead but I don't want to do that
+        for _ in 0..num_random_packs {
+            let idx = rng.below(all_set_selection_list.len());
+            set_list.push(all_set_selection_list[idx].clone());
+        }
+        shuffle(&mut set_list, rng);
+        let init_set = set_list.first().cloned().ok_or(DraftError::NoPacks)?;
+        self.pack_number = 0;
+        self.pack_series = set_list;
+        self.prepare_set(init_set.as_str())
+    }
+    /// Fills the rarity pools from a set's JSON, which maps card names to objects with:
+    ///
+    /// * `c` converted mana cost, `r` rarity (`m`, `r`, `u` or `c`)
+    /// * optionally `i` multiverse id, `ci` color identity letters, `mc` mana cost, `t` type line,
+    ///   `p`/`th` power and toughness, `o` rules text, and `mo` MTGO catalog id
+    ///
+    /// When `ci` is missing the colors are taken from the mana cost instead.
+    fn prepare_set(&mut self, set_name:&str) -> Result<(), DraftError> {
+        let set_json = self.all_set_json.get(set_name).ok_or_else(|| DraftError::UnknownSet(String::from(set_name)))?
+            .as_object().ok_or_else(|| invalid_set(set_name, None, IssueKind::NotASet))?;
+        // every pool starts empty, or packs of this set would pull cards left over from the last
+        self.mythics = vec![];
+        self.rares = vec![];
+        self.uncommons = vec![];
+        self.commons = vec![];
+        self.basics = vec![];
+        for card_name in set_json.keys() {
+            let invalid_card = |kind| invalid_set(set_name, Some(card_name), kind);
+            let value = set_json[card_name].as_object().ok_or_else(|| invalid_card(IssueKind::NotACard))?;
+            let cmc = value.get("c").ok_or_else(|| invalid_card(IssueKind::MissingField("c")))?;
+            let cmc = cmc.as_i64().ok_or_else(|| invalid_card(IssueKind::NonIntegerCmc(cmc.clone())))?;
+            let rarity = value.get("r").ok_or_else(|| invalid_card(IssueKind::MissingField("r")))?;
+            let rarity = rarity.as_str().ok_or_else(|| invalid_card(IssueKind::UnknownRarity(rarity.to_string())))?;
+            let url = match value.get("i").and_then(Value::as_i64) {
+                Some(id) => get_img_url(id),
+                None => get_named_img_url(card_name)
+            };
+            let text_field = |key:&str| value.get(key).and_then(Value::as_str).map(String::from);
+            let mana_cost = text_field("mc");
+            let colors = match text_field("ci") {
+                Some(colors) => colors,
+                None => colors_from_mana_cost(mana_cost.as_deref().unwrap_or(""))
+            };
+            let card :Card= Card{
+                name:String::from(card_name),
+                cmc:cmc as i16,
+                img_url:url,
+                colors,
+                mana_cost,
+                type_line:text_field("t"),
+                power:text_field("p"),
+                toughness:text_field("th"),
+                text:text_field("o"),
+                rarity:rarity.chars().next().unwrap_or('c'),
+                set:String::from(set_name),
+                mtgo_id:value.get("mo").and_then(Value::as_i64)
+            };
+            if self.is_basic(card_name) {
+                self.basics.push(card)
+            } else {
+                match rarity {
+                    "r" => self.rares.push(card),
+                    "m" => self.mythics.push(card),
+                    "u" => self.uncommons.push(card),
+                    "c" => self.commons.push(card),
+                    _ => {}
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn progress_pack(&mut self) -> Result<(), DraftError> {
+        let current_set = self.pack_series[self.pack_number as usize].clone();
+        self.pack_number += 1;
+        if self.pack_number < self.pack_series.len() as i16 {
+            let next_set = self.pack_series[self.pack_number as usize].clone();
+            if current_set!=next_set {
+                return self.prepare_set(next_set.as_str());
+            }
+        }
+        Ok(())
+    }
+
+    /// How many cards each rarity pool of the current set holds, `b` being the basic lands.
+    pub fn pool_sizes(&self) -> BTreeMap<char, usize> {
+        RARITY_ORDER.iter().chain(['b'].iter()).map(|rarity| (*rarity, self.pool(*rarity).len())).collect()
+    }
+
+    fn pool(&self, rarity:char) -> &[Card] {
+        match rarity {
+            'm' => &self.mythics,
+            'r' => &self.rares,
+            'u' => &self.uncommons,
+            'c' => &self.commons,
+            'b' => &self.basics,
+            _ => &[]
+        }
+    }
+
+    /// The template for the set of the pack being opened.
+    pub fn current_template(&self) -> &PackTemplate {
+        self.pack_series.get(self.pack_number as usize)
+            .and_then(|set| self.templates.get(set))
+            .unwrap_or(&self.default_template)
+    }
+
+    /// Weighted candidate cards for a slot. A slot whose rarities are all empty
+    /// (cube lists have no rares) draws from the next non-empty rarity below them.
+    fn slot_candidates(&self, slot:&PackSlot) -> Vec<(u32, Vec<&Card>)> {
+        let filtered = |rarity:char| self.pool(rarity).iter().filter(|card| slot.filter.matches(card)).collect::<Vec<_>>();
+        let candidates:Vec<(u32, Vec<&Card>)> = slot.rarities.iter()
+            .map(|(rarity, weight)| (*weight, filtered(*rarity)))
+            .filter(|(weight, cards)| *weight > 0 && !cards.is_empty())
+            .collect();
+        if candidates.is_empty() {
+            if let Some(lowest) = RARITY_ORDER.iter().rposition(|rarity| slot.rarities.contains_key(rarity)) {
+                for rarity in &RARITY_ORDER[lowest+1..] {
+                    let cards = filtered(*rarity);
+                    if !cards.is_empty() {
+                        return vec![(1, cards)];
+                    }
+                }
+            }
+        }
+        candidates
+    }
+
+    fn generate_card(&self, candidates:&[(u32, Vec<&Card>)], rng:&mut dyn DraftRng) -> Card {
+        let total_weight:u32 = candidates.iter().map(|(weight, _)| weight).sum();
+        let mut roll = rng.below(total_weight as usize) as u32;
+        for (weight, cards) in candidates {
+            if roll < *weight {
+                return cards[rng.below(cards.len())].clone();
+            }
+            roll -= weight;
+        }
+        unreachable!("roll is below the total weight")
+    }
+
+    /// Unpicked cards of the rarity nearest the slot's own that has any, lower
+    /// rarities first, for a slot whose pools are used up.
+    fn neighboring_candidates(&self, slot:&PackSlot, cards:&[Card]) -> Vec<(u32, Vec<&Card>)> {
+        let slot_idxs:Vec<usize> = (0..RARITY_ORDER.len()).filter(|idx| slot.rarities.contains_key(&RARITY_ORDER[*idx])).collect();
+        let mut neighbors:Vec<usize> = (0..RARITY_ORDER.len()).filter(|idx| !slot_idxs.contains(idx)).collect();
+        neighbors.sort_by_key(|idx| (slot_idxs.iter().map(|slot_idx| slot_idx.abs_diff(*idx)).min().unwrap_or(0), std::cmp::Reverse(*idx)));
+        neighbors.into_iter()
+            .map(|idx| self.pool(RARITY_ORDER[idx]).iter().filter(|card| slot.filter.matches(card) && !cards.contains(card)).collect::<Vec<_>>())
+            .find(|cards| !cards.is_empty())
+            .map(|cards| vec![(1, cards)])
+            .unwrap_or_default()
+    }
+
+    /// Opens a pack of the current set, resized to `pack_size` cards if given.
+    ///
+    /// Slots that may not repeat cards draw only cards not yet in the pack. When
+    /// those run out the slot borrows from a neighboring rarity, and failing
+    /// that repeats cards, noting either in the pack's `short_slots`.
+    pub fn generate_pack(&self, pack_size:Option<u16>, rng:&mut dyn DraftRng) -> Pack {
+        let template = match pack_size {
+            Some(pack_size) => self.current_template().resized(pack_size),
+            None => self.current_template().clone()
+        };
+        let mut cards:Vec<Card> = vec![];
+        let mut short_slots = vec![];
+        for slot in &template.slots {
+            let candidates = self.slot_candidates(slot);
+            if candidates.is_empty() {
+                // e.g. the basic land slot of a set without basics
+                continue;
+            }
+            let mut short = ShortSlot{slot:slot.name.clone(), ..ShortSlot::default()};
+            for _ in 0..slot.count {
+                if slot.duplicates {
+                    cards.push(self.generate_card(&candidates, rng));
+                    continue;
+                }
+                let unpicked:Vec<(u32, Vec<&Card>)> = candidates.iter()
+                    .map(|(weight, pool)| (*weight, pool.iter().filter(|card| !cards.contains(card)).cloned().collect::<Vec<_>>()))
+                    .filter(|(_, pool)| !pool.is_empty())
+                    .collect();
+                let card = if !unpicked.is_empty() {
+                    self.generate_card(&unpicked, rng)
+                } else {
+                    let neighbors = self.neighboring_candidates(slot, &cards);
+                    if neighbors.is_empty() {
+                        short.repeated += 1;
+                        self.generate_card(&candidates, rng)
+                    } else {
+                        short.borrowed += 1;
+                        self.generate_card(&neighbors, rng)
+                    }
+                };
+                cards.push(card);
+            }
+            if short.borrowed + short.repeated > 0 {
+                short_slots.push(short);
+            }
+        }
+        Pack{cards, num_picks:0, seen:vec![], short_slots}
+    }
+}
+
+fn picks_left(format:&DraftFormat, pack:&Pack) -> usize {
+    let remaining = (format.picks_per_pack - pack.num_picks).max(0) as usize;
+    remaining.min(pack.cards.len())
+}
+
+/// `Draft::pick_context`, from the fields it reads.
+fn human_context<'a>(format:&DraftFormat, pack:&Pack, generator:&SetGenerator, ratings:&'a SetRatings) -> PickContext<'a> {
+    PickContext{num_picks:picks_left(format, pack), pack_number:generator.pack_number, num_packs:format.num_packs, ratings}
+}
+
+/// The human drafter's run through a series of packs, alone or in a pod of bots.
+///
+/// `start` lays out the pack series from a setup, after which the drafter
+/// repeatedly `pick`s from `current_pack` until `is_finished`. All randomness
+/// comes from `rng`, so the same seed and setup replay the same draft.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Draft<R:DraftRng = SeededRng> {
+    generator:SetGenerator,
+    rng:R,
+    #[serde(default)]
+    format:DraftFormat,
+    pack:Pack,
+    #[serde(default)]
+    pod:Pod,
+    /// The human's picks in the order they were made; views group them with `grouping::group_cards`.
+    #[serde(default)]
+    picks:Vec<Card>,
+    // drafts saved before picks were kept in order have them bucketed by CMC instead
+    #[serde(default, rename = "sorted_picks", skip_serializing)]
+    legacy_sorted_picks:Vec<Vec<Card>>,
+    #[serde(default)]
+    history:Vec<PickRecord>,
+    // milliseconds since the epoch for history timestamps; the engine has no clock of its own
+    #[serde(skip)]
+    clock:Option<fn() -> f64>,
+    // loaded separately like the set data, see `Draft::resume`
+    #[serde(skip)]
+    ratings:SetRatings
+}
+
+impl<R:DraftRng> Draft<R> {
+    pub fn new (generator:SetGenerator, rng:R) -> Self {
+        Draft{generator, rng, format:DraftFormat::default(), pack:Pack::default(), pod:Pod::default(), picks:vec![], legacy_sorted_picks:vec![], history:vec![], clock:None, ratings:SetRatings::default()}
+    }
+
+    /// Replaces the random source, e.g. to reseed before `start`.
+    pub fn set_rng(&mut self, rng:R) {
+        self.rng = rng;
+    }
+
+    /// Continues a previously saved draft, keeping this draft's set data, templates and ratings since saved drafts carry none.
+    pub fn resume(&mut self, saved:Draft<R>) {
+        let all_set_json = std::mem::take(&mut self.generator.all_set_json);
+        let templates = std::mem::take(&mut self.generator.templates);
+        let ratings = std::mem::take(&mut self.ratings);
+        let clock = self.clock;
+        *self = saved;
+        self.clock = clock;
+        self.generator.all_set_json = all_set_json;
+        self.generator.templates = templates;
+        self.ratings = ratings;
+        if self.picks.is_empty() {
+            self.picks = std::mem::take(&mut self.legacy_sorted_picks).into_iter().flatten().collect();
+        }
+    }
+
+    /// Gives the draft a clock, in milliseconds since the Unix epoch, to timestamp its history with.
+    pub fn set_clock(&mut self, clock:fn() -> f64) {
+        self.clock = Some(clock);
+    }
+
+    pub fn set_ratings(&mut self, ratings:SetRatings) {
+        self.ratings = ratings;
+    }
+
+    pub fn ratings(&self) -> &SetRatings {
+        &self.ratings
+    }
+
+    /// Sets the bots' pick strategies in seat order; call after `start`.
+    pub fn set_bot_strategies(&mut self, strategies:&[StrategyKind]) {
+        self.pod.set_strategies(strategies);
+    }
+
+    pub fn generator(&self) -> &SetGenerator {
+        &self.generator
+    }
+
+    pub fn add_sets(&mut self, sets:Map<String, Value>) {
+        self.generator.add_sets(sets);
+    }
+
+    /// Begins a fresh draft; packs not assigned by the setup are filled with random sets from it.
+    pub fn start(&mut self, setup_list:&[(String, i16)], format:DraftFormat) -> Result<(), DraftError> {
+        format.check()?;
+        let num_random_packs = num_random_packs(setup_list, format.num_packs);
+        if num_random_packs < 0 {
+            return Err(DraftError::TooManyPacks{assigned:format.num_packs - num_random_packs, num_packs:format.num_packs});
+        }
+        self.generator.init_from_setup_packs(setup_list, num_random_packs, &mut self.rng)?;
+        self.format = format;
+        self.pack = self.generator.generate_pack(format.pack_size, &mut self.rng);
+        self.pod = Pod::new(format.seats.saturating_sub(1) as usize);
+        self.pod.open_packs(&self.generator, format.pack_size, &mut self.rng);
+        self.picks = vec![];
+        self.history = vec![];
+        self.check_pack()
+    }
+
+    pub fn current_pack(&self) -> &Pack {
+        &self.pack
+    }
+
+    pub fn pack_number(&self) -> i16 {
+        self.generator.pack_number
+    }
+
+    pub fn format(&self) -> DraftFormat {
+        self.format
+    }
+
+    /// The bots' seats, in passing-left order; empty when drafting alone.
+    pub fn bots(&self) -> &[Seat] {
+        &self.pod.bots
+    }
+
+    pub fn pass_direction(&self) -> PassDirection {
+        PassDirection::for_round(self.generator.pack_number)
+    }
+
+    /// Cards in the current pack that were already in it when the human passed it on, i.e. that wheeled.
+    pub fn wheeled_cards(&self) -> Vec<&Card> {
+        self.pack.cards.iter().filter(|card| self.pack.seen.contains(&card.name)).collect()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.generator.pack_number >= self.format.num_packs
+    }
+
+    /// Cards still to be taken from the current pack, fewer than the format asks for if the pack runs out.
+    pub fn picks_required(&self) -> usize {
+        picks_left(&self.format, &self.pack)
+    }
+
+    /// One record per pack the human has picked from, oldest first.
+    pub fn history(&self) -> &[PickRecord] {
+        &self.history
+    }
+
+    /// Every card picked so far, in pick order.
+    pub fn picks(&self) -> &[Card] {
+        &self.picks
+    }
+
+    /// Takes the named cards out of the current pack, opening the next pack once enough have been taken.
+    ///
+    /// Fails only if the next pack's set cannot be read.
+    pub fn pick(&mut self, names:&[String]) -> Result<(), DraftError> {
+        for name in names {
+            self.pick_card(name)?;
+        }
+        Ok(())
+    }
+
+    /// Makes the human's next pick using `strategy`, for auto-picking and headless drafts.
+    pub fn pick_with(&mut self, strategy:&dyn PickStrategy) -> Result<(), DraftError> {
+        // borrows only the fields the context needs, leaving the rng free for the strategy
+        let context = human_context(&self.format, &self.pack, &self.generator, &self.ratings);
+        let names = strategy.choose(&self.pack, &self.picks, &context, &mut self.rng);
+        self.pick(&names)
+    }
+
+    /// What a strategy choosing the human's next pick gets to know.
+    pub fn pick_context(&self) -> PickContext<'_> {
+        human_context(&self.format, &self.pack, &self.generator, &self.ratings)
+    }
+
+    fn pick_card(&mut self, name:&str) -> Result<(), DraftError> {
+        if let Some(card_idx) = self.pack.cards.iter().position(|card| card.name == name) {
+            if self.pack.num_picks == 0 {
+                let set = self.generator.pack_series.get(self.generator.pack_number as usize).cloned().unwrap_or_default();
+                let timestamp = self.clock.map(|clock| clock());
+                self.history.push(PickRecord{pack_number:self.generator.pack_number, set, contents:self.pack.cards.clone(), picked:vec![], timestamp});
+            }
+            if let Some(record) = self.history.last_mut() {
+                record.picked.push(String::from(name));
+            }
+            let card = self.pack.cards.remove(card_idx);
+            self.picks.push(card);
+            self.pack.num_picks += 1;
+            if self.pack.num_picks>=self.format.picks_per_pack || self.pack.cards.is_empty() {
+                if self.pod.is_empty() {
+                    return self.generate_next_pack();
+                } else {
+                    return self.pass_packs();
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn pass_packs (&mut self) -> Result<(), DraftError> {
+        let context = PickContext{num_picks:self.format.picks_per_pack as usize, pack_number:self.generator.pack_number, num_packs:self.format.num_packs, ratings:&self.ratings};
+        self.pod.bots_pick(&context, &mut self.rng);
+        self.pack.seen = self.pack.cards.iter().map(|card| card.name.clone()).collect();
+        let direction = self.pass_direction();
+        self.pod.pass(&mut self.pack, direction);
+        // every seat takes the same number of cards, so all packs run out together
+        if self.pack.cards.is_empty() {
+            return self.generate_next_pack();
+        }
+        Ok(())
+    }
+
+    fn generate_next_pack (&mut self) -> Result<(), DraftError> {
+        self.generator.progress_pack()?;
+        if self.generator.pack_number < self.format.num_packs {
+            self.pack = self.generator.generate_pack(self.format.pack_size, &mut self.rng);
+            self.pod.open_packs(&self.generator, self.format.pack_size, &mut self.rng);
+            self.check_pack()
+        } else {
+            self.pack = Pack::default();
+            Ok(())
+        }
+    }
+
+    /// A freshly opened pack with nothing in it could never be picked from, so the draft stops there.
+    fn check_pack(&self) -> Result<(), DraftError> {
+        if self.pack.cards.is_empty() {
+            let set = self.generator.pack_series.get(self.generator.pack_number as usize).cloned().unwrap_or_default();
+            return Err(DraftError::EmptyPack(set));
+        }
+        Ok(())
+    }
+}
+
+impl Draft<SeededRng> {
+    /// Starts the draft `setup` describes, reseeding first so the same setup always opens the same packs.
+    pub fn start_setup(&mut self, setup:&SetupInfo) -> Result<(), DraftError> {
+        self.set_rng(SeededRng::new(setup.seed));
+        self.start(&setup.sets, setup.format)?;
+        self.set_bot_strategies(&setup.bot_strategies);
+        Ok(())
+    }
+}
+
+impl<R:DraftRng + Clone> Draft<R> {
+    /// A copy of the draft as it stands, without the set data, templates and
+    /// ratings that `resume` carries over, so it is cheap enough to keep one
+    /// per pick for undoing. Going back to it is `resume(snapshot)`.
+    pub fn snapshot(&self) -> Draft<R> {
+        Draft{
+            generator:self.generator.clone_state(),
+            rng:self.rng.clone(),
+            format:self.format,
+            pack:self.pack.clone(),
+            pod:self.pod.clone(),
+            picks:self.picks.clone(),
+            legacy_sorted_picks:vec![],
+            history:self.history.clone(),
+            clock:None,
+            ratings:SetRatings::default()
+        }
+    }
+}
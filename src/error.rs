@@ -0,0 +1,41 @@
+//! Why a draft could not be set up or continued.
+
+use std::fmt;
+
+use crate::validate::Issue;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum DraftError {
+    /// Set data or pack templates that could not be read at all.
+    BadData(String),
+    UnknownSet(String),
+    /// The first problem found in a set the pack series uses.
+    InvalidSet(Issue),
+    /// The setup lists no sets to open packs from.
+    NoSets,
+    /// The setup and the random packs together open no packs at all.
+    NoPacks,
+    /// A format that cannot be drafted, such as one with no packs or no picks.
+    BadFormat(String),
+    /// A pack of the named set came out with no cards to pick.
+    EmptyPack(String),
+    /// The setup assigns more packs than the format has.
+    TooManyPacks{assigned:i16, num_packs:i16}
+}
+
+impl fmt::Display for DraftError {
+    fn fmt(&self, f:&mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DraftError::BadData(problem) => write!(f, "The card data could not be read: {}", problem),
+            DraftError::UnknownSet(set) => write!(f, "There is no set called {}", set),
+            DraftError::InvalidSet(issue) => write!(f, "{} cannot be drafted ({})", issue.set, issue),
+            DraftError::NoSets => write!(f, "Choose at least one set to draft"),
+            DraftError::NoPacks => write!(f, "The draft has no packs to open"),
+            DraftError::BadFormat(problem) => write!(f, "The draft format is not valid: {}", problem),
+            DraftError::EmptyPack(set) => write!(f, "A pack of {} has no cards to pick", set),
+            DraftError::TooManyPacks{assigned, num_packs} => write!(f, "The sets add up to {} packs, but the draft only has {}", assigned, num_packs)
+        }
+    }
+}
+
+impl std::error::Error for DraftError {}
@@ -0,0 +1,210 @@
+//! Turning a decklist into the text other Magic clients import.
+//!
+//! Every format takes the main deck and sideboard as plain card lists; copies
+//! are counted by name, or by name and printing for formats that name one, and
+//! listed alphabetically unless the format says otherwise, so the same deck
+//! always exports to the same text. The fixtures in `tests/fixtures/export`
+//! follow files saved by each client.
+
+use crate::draft::Card;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// MTG Arena: `Deck` and `Sideboard` sections of `N Name (SET)` lines.
+    #[default]
+    Arena,
+    /// MTGO `.dek` XML. MTGO finds cards by `CatID`, which only set data with
+    /// `mo` ids gives; for other sets the by-name text export, which MTGO also
+    /// imports, is the better choice.
+    Mtgo,
+    /// Cockatrice `.cod` XML with `main` and `side` zones.
+    Cockatrice,
+    /// Forge `.dck`, with `N Name|SET` lines under `[Main]` and `[Sideboard]`.
+    Forge,
+    /// `N Name` lines ordered by mana value, then name.
+    TextByCmc,
+    /// `N Name` lines ordered by name.
+    TextByName
+}
+
+impl ExportFormat {
+    pub const ALL:[ExportFormat; 6] = [ExportFormat::Arena, ExportFormat::Mtgo, ExportFormat::Cockatrice, ExportFormat::Forge, ExportFormat::TextByCmc, ExportFormat::TextByName];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ExportFormat::Arena => "MTG Arena",
+            ExportFormat::Mtgo => "MTGO (.dek)",
+            ExportFormat::Cockatrice => "Cockatrice (.cod)",
+            ExportFormat::Forge => "Forge (.dck)",
+            ExportFormat::TextByCmc => "Text by mana value",
+            ExportFormat::TextByName => "Text by name"
+        }
+    }
+
+    /// Short name used in form values and on the command line.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ExportFormat::Arena => "arena",
+            ExportFormat::Mtgo => "mtgo",
+            ExportFormat::Cockatrice => "cockatrice",
+            ExportFormat::Forge => "forge",
+            ExportFormat::TextByCmc => "cmc",
+            ExportFormat::TextByName => "name"
+        }
+    }
+
+    pub fn from_code(code:&str) -> Option<Self> {
+        ExportFormat::ALL.iter().find(|format| format.code() == code).cloned()
+    }
+
+    /// File extension, without the dot, for saving an export.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Mtgo => "dek",
+            ExportFormat::Cockatrice => "cod",
+            ExportFormat::Forge => "dck",
+            _ => "txt"
+        }
+    }
+}
+
+/// File name for a downloaded export, e.g. `MH1-WAR-draft-2020-09-01.dek`.
+///
+/// `date` is expected as `YYYY-MM-DD`; characters that are awkward in file names are dropped from set names.
+pub fn export_file_name(sets:&[&str], date:&str, format:ExportFormat) -> String {
+    let mut parts:Vec<String> = sets.iter()
+        .map(|set| set.chars().filter(|c| c.is_ascii_alphanumeric() || *c == '_').collect::<String>())
+        .filter(|set| !set.is_empty())
+        .collect();
+    parts.push(String::from("draft"));
+    if !date.is_empty() {
+        parts.push(String::from(date));
+    }
+    format!("{}.{}", parts.join("-"), format.extension())
+}
+
+/// Renders the deck in `format`; `deck_name` is used by the formats that store one.
+pub fn export_deck(format:ExportFormat, deck_name:&str, main:&[Card], sideboard:&[Card]) -> String {
+    match format {
+        ExportFormat::Arena => export_arena(main, sideboard),
+        ExportFormat::Mtgo => export_mtgo(main, sideboard),
+        ExportFormat::Cockatrice => export_cockatrice(deck_name, main, sideboard),
+        ExportFormat::Forge => export_forge(deck_name, main, sideboard),
+        ExportFormat::TextByCmc => export_text(main, sideboard, true),
+        ExportFormat::TextByName => export_text(main, sideboard, false)
+    }
+}
+
+/// Copies of each card, in name order, counted apart when `printing` differs.
+fn count_cards<K:Ord>(cards:&[Card], printing:impl Fn(&Card) -> K) -> Vec<(usize, &Card)> {
+    let mut sorted:Vec<&Card> = cards.iter().collect();
+    sorted.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| printing(a).cmp(&printing(b))));
+    let mut counted:Vec<(usize, &Card)> = vec![];
+    for card in sorted {
+        match counted.last_mut() {
+            Some((count, last)) if last.name == card.name && printing(last) == printing(card) => *count += 1,
+            _ => counted.push((1, card))
+        }
+    }
+    counted
+}
+
+fn by_set(card:&Card) -> String {
+    card.set.clone()
+}
+
+fn by_name(_:&Card) {}
+
+fn arena_line(count:usize, card:&Card) -> String {
+    if card.set.is_empty() {
+        format!("{} {}", count, card.name)
+    } else {
+        format!("{} {} ({})", count, card.name, card.set)
+    }
+}
+
+fn export_arena(main:&[Card], sideboard:&[Card]) -> String {
+    let mut lines = vec![String::from("Deck")];
+    lines.extend(count_cards(main, by_set).into_iter().map(|(count, card)| arena_line(count, card)));
+    if !sideboard.is_empty() {
+        lines.push(String::new());
+        lines.push(String::from("Sideboard"));
+        lines.extend(count_cards(sideboard, by_set).into_iter().map(|(count, card)| arena_line(count, card)));
+    }
+    lines.join("\n") + "\n"
+}
+
+/// Escapes text for a double quoted attribute, leaving apostrophes as both clients write them.
+fn escape_xml(text:&str) -> String {
+    text.replace('&', "&amp;").replace('"', "&quot;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+fn export_mtgo(main:&[Card], sideboard:&[Card]) -> String {
+    let mut lines = vec![
+        String::from(r#"<?xml version="1.0" encoding="utf-8"?>"#),
+        String::from(r#"<Deck xmlns:xsd="http://www.w3.org/2001/XMLSchema" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance">"#),
+        String::from("  <NetDeckID>0</NetDeckID>"),
+        String::from("  <PreconstructedDeckID>0</PreconstructedDeckID>")
+    ];
+    for (cards, is_sideboard) in [(main, false), (sideboard, true)] {
+        for (count, card) in count_cards(cards, |card| card.mtgo_id) {
+            let cat_id = card.mtgo_id.map(|id| format!(r#"CatID="{}" "#, id)).unwrap_or_default();
+            lines.push(format!(r#"  <Cards {}Quantity="{}" Sideboard="{}" Name="{}" Annotation="0" />"#, cat_id, count, is_sideboard, escape_xml(&card.name)));
+        }
+    }
+    lines.push(String::from("</Deck>"));
+    lines.join("\n") + "\n"
+}
+
+fn export_cockatrice(deck_name:&str, main:&[Card], sideboard:&[Card]) -> String {
+    let mut lines = vec![
+        String::from(r#"<?xml version="1.0" encoding="UTF-8"?>"#),
+        String::from(r#"<cockatrice_deck version="1">"#),
+        format!("    <deckname>{}</deckname>", escape_xml(deck_name)),
+        String::from("    <comments></comments>")
+    ];
+    for (cards, zone) in [(main, "main"), (sideboard, "side")] {
+        if cards.is_empty() {
+            continue;
+        }
+        lines.push(format!(r#"    <zone name="{}">"#, zone));
+        for (count, card) in count_cards(cards, by_name) {
+            lines.push(format!(r#"        <card number="{}" name="{}"/>"#, count, escape_xml(&card.name)));
+        }
+        lines.push(String::from("    </zone>"));
+    }
+    lines.push(String::from("</cockatrice_deck>"));
+    lines.join("\n") + "\n"
+}
+
+fn export_forge(deck_name:&str, main:&[Card], sideboard:&[Card]) -> String {
+    let mut lines = vec![String::from("[metadata]"), format!("Name={}", deck_name)];
+    for (cards, section) in [(main, "[Main]"), (sideboard, "[Sideboard]")] {
+        lines.push(String::from(section));
+        for (count, card) in count_cards(cards, by_set) {
+            if card.set.is_empty() {
+                lines.push(format!("{} {}", count, card.name));
+            } else {
+                lines.push(format!("{} {}|{}", count, card.name, card.set));
+            }
+        }
+    }
+    lines.join("\n") + "\n"
+}
+
+fn export_text(main:&[Card], sideboard:&[Card], by_cmc:bool) -> String {
+    let text_lines = |cards:&[Card]| {
+        let mut counted = count_cards(cards, by_name);
+        if by_cmc {
+            // stable, so names stay in order within each mana value
+            counted.sort_by_key(|(_, card)| card.cmc);
+        }
+        counted.into_iter().map(|(count, card)| format!("{} {}", count, card.name)).collect::<Vec<_>>()
+    };
+    let mut lines = text_lines(main);
+    if !sideboard.is_empty() {
+        lines.push(String::new());
+        lines.extend(text_lines(sideboard));
+    }
+    lines.join("\n") + "\n"
+}
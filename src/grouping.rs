@@ -0,0 +1,111 @@
+//! Views of a pool grouped into columns, computed from the picks in pick order.
+//!
+//! The pool itself is never re-sorted; each view is built on demand, so
+//! switching between groupings loses nothing.
+
+use crate::draft::Card;
+
+/// Cards per column when laying out picks in pick order.
+pub const PICK_ORDER_COLUMN:usize = 10;
+
+const CARD_TYPES:[&str; 7] = ["Creature", "Planeswalker", "Instant", "Sorcery", "Enchantment", "Artifact", "Land"];
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Grouping {
+    #[default]
+    Cmc,
+    Color,
+    Type,
+    Rarity,
+    PickOrder,
+    Set
+}
+
+impl Grouping {
+    pub const ALL:[Grouping; 6] = [Grouping::Cmc, Grouping::Color, Grouping::Type, Grouping::Rarity, Grouping::PickOrder, Grouping::Set];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Grouping::Cmc => "Mana value",
+            Grouping::Color => "Color",
+            Grouping::Type => "Card type",
+            Grouping::Rarity => "Rarity",
+            Grouping::PickOrder => "Pick order",
+            Grouping::Set => "Set"
+        }
+    }
+
+    /// Short name used in form values.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Grouping::Cmc => "cmc",
+            Grouping::Color => "color",
+            Grouping::Type => "type",
+            Grouping::Rarity => "rarity",
+            Grouping::PickOrder => "pick",
+            Grouping::Set => "set"
+        }
+    }
+
+    pub fn from_code(code:&str) -> Option<Self> {
+        Grouping::ALL.iter().find(|grouping| grouping.code() == code).cloned()
+    }
+}
+
+/// Labelled columns of `cards`, leaving out empty ones.
+///
+/// Cards keep pick order in pick order columns and are sorted by name in every other view.
+pub fn group_cards(cards:&[Card], grouping:Grouping) -> Vec<(String, Vec<&Card>)> {
+    if grouping == Grouping::PickOrder {
+        return cards.chunks(PICK_ORDER_COLUMN).enumerate()
+            .map(|(idx, chunk)| {
+                let first = idx * PICK_ORDER_COLUMN + 1;
+                (format!("Picks {}–{}", first, first + chunk.len() - 1), chunk.iter().collect())
+            })
+            .collect();
+    }
+    // (sort key, label) per card, so columns come out in a sensible order rather than alphabetically
+    let mut columns:Vec<((usize, String), Vec<&Card>)> = vec![];
+    for card in cards {
+        let key = group_key(card, grouping, cards);
+        match columns.iter_mut().find(|(existing, _)| *existing == key) {
+            Some((_, column)) => column.push(card),
+            None => columns.push((key, vec![card]))
+        }
+    }
+    columns.sort_by(|a, b| a.0.cmp(&b.0));
+    columns.into_iter()
+        .map(|((_, label), mut column)| {
+            column.sort_by(|a, b| a.name.cmp(&b.name));
+            (label, column)
+        })
+        .collect()
+}
+
+fn group_key(card:&Card, grouping:Grouping, cards:&[Card]) -> (usize, String) {
+    match grouping {
+        Grouping::Cmc => (card.cmc.max(0) as usize, card.cmc.max(0).to_string()),
+        Grouping::Color => match card.colors.len() {
+            0 => (6, String::from("Colorless")),
+            1 => ("WUBRG".find(&card.colors[..]).unwrap_or(5), card.colors.clone()),
+            _ => (5, String::from("Multicolor"))
+        },
+        Grouping::Type => match CARD_TYPES.iter().position(|card_type| card.has_type(card_type)) {
+            Some(idx) => (idx, String::from(CARD_TYPES[idx])),
+            None => (CARD_TYPES.len(), String::from("Other"))
+        },
+        Grouping::Rarity => match card.rarity {
+            'm' => (0, String::from("Mythic")),
+            'r' => (1, String::from("Rare")),
+            'u' => (2, String::from("Uncommon")),
+            'c' => (3, String::from("Common")),
+            _ => (4, String::from("Other"))
+        },
+        // sets in the order they were first picked from
+        Grouping::Set => {
+            let label = if card.set.is_empty() {String::from("Unknown")} else {card.set.clone()};
+            (cards.iter().position(|other| other.set == card.set).unwrap_or(0), label)
+        }
+        Grouping::PickOrder => (0, String::new())
+    }
+}
@@ -0,0 +1,30 @@
+//! A record of every pack the human was shown and what they took from it.
+
+use serde::{Serialize, Deserialize};
+
+use crate::draft::{Card, SetupInfo};
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct PickRecord {
+    /// Zero based, as `Draft::pack_number`.
+    pub pack_number:i16,
+    pub set:String,
+    /// The pack as it was shown, before any of this round's picks.
+    pub contents:Vec<Card>,
+    /// Names of the cards taken, in the order they were taken.
+    pub picked:Vec<String>,
+    /// Milliseconds since the Unix epoch of the round's first pick, if the draft has a clock.
+    #[serde(default)]
+    pub timestamp:Option<f64>
+}
+
+#[derive(Serialize)]
+struct HistoryExport<'a> {
+    setup:&'a SetupInfo,
+    picks:&'a [PickRecord]
+}
+
+/// The setup and pick history as pretty printed JSON, for saving or sharing after the draft.
+pub fn history_to_json(setup:&SetupInfo, history:&[PickRecord]) -> String {
+    serde_json::to_string_pretty(&HistoryExport{setup, picks:history}).unwrap_or_default()
+}
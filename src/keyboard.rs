@@ -0,0 +1,77 @@
+//! Keyboard shortcuts for picking from the pack grid.
+//!
+//! Keys are matched on `KeyboardEvent.key` values, so the mapping can be
+//! checked without a browser; the page only forwards presses to `key_action`.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    Left,
+    Right,
+    Up,
+    Down
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyAction {
+    /// Moves the focused card within the grid.
+    Move(Direction),
+    /// Selects or deselects the focused card.
+    Toggle,
+    Confirm,
+    /// Selects or deselects the card at this zero based position in the pack.
+    ToggleIndex(usize),
+    ToggleHelp,
+    CloseHelp
+}
+
+/// Keys and what they do, for the help overlay.
+pub const SHORTCUTS:[(&str, &str); 6] = [
+    ("← → ↑ ↓", "Move between cards"),
+    ("Space / Enter", "Select or deselect the highlighted card"),
+    ("1 – 9, 0", "Select or deselect the first to tenth card"),
+    ("C", "Confirm the pick"),
+    ("?", "Show or hide this help"),
+    ("Esc", "Close this help")
+];
+
+pub fn key_action(key:&str) -> Option<KeyAction> {
+    let action = match key {
+        "ArrowLeft" => KeyAction::Move(Direction::Left),
+        "ArrowRight" => KeyAction::Move(Direction::Right),
+        "ArrowUp" => KeyAction::Move(Direction::Up),
+        "ArrowDown" => KeyAction::Move(Direction::Down),
+        " " | "Enter" => KeyAction::Toggle,
+        "c" | "C" => KeyAction::Confirm,
+        "0" => KeyAction::ToggleIndex(9),
+        "?" => KeyAction::ToggleHelp,
+        "Escape" => KeyAction::CloseHelp,
+        _ => {
+            let digit = key.parse::<usize>().ok().filter(|digit| (1..=9).contains(digit))?;
+            KeyAction::ToggleIndex(digit - 1)
+        }
+    };
+    Some(action)
+}
+
+/// The card focused after moving from `focus` in a grid of `len` cards laid out `columns` wide.
+///
+/// Nothing focused yet lands on the first card, and moves off the edge of the grid stay put.
+pub fn move_focus(focus:Option<usize>, len:usize, columns:usize, direction:Direction) -> Option<usize> {
+    if len == 0 {
+        return None;
+    }
+    let focus = match focus {
+        Some(focus) => focus.min(len - 1),
+        None => return Some(0)
+    };
+    let columns = columns.max(1);
+    let moved = match direction {
+        Direction::Left if focus % columns > 0 => focus - 1,
+        Direction::Right if focus % columns + 1 < columns && focus + 1 < len => focus + 1,
+        Direction::Up if focus >= columns => focus - columns,
+        // the last row may be short, so moving down from above its gap lands on the last card
+        Direction::Down if focus / columns < (len - 1) / columns => (focus + columns).min(len - 1),
+        _ => focus
+    };
+    Some(moved)
+}
@@ -1,9 +1,29 @@
 #![recursion_limit="2048"]
 mod utils;
 mod card_display;
+pub mod draft;
+pub mod rng;
+pub mod share;
+pub mod custom_sets;
+pub mod template;
+pub mod pod;
+pub mod ratings;
+pub mod strategy;
+pub mod export;
+pub mod deck;
+pub mod stats;
+pub mod grouping;
+pub mod history;
+pub mod undo;
+pub mod keyboard;
+pub mod cli;
+pub mod simulate;
+pub mod validate;
+pub mod error;
+mod storage;
+mod browser;
 
 use wasm_bindgen::prelude::*;
-use std::collections::HashMap;
 
 use card_display::CardDisplay;
 
@@ -13,51 +33,113 @@ use card_display::CardDisplay;
 #[global_allocator]
 static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
 
-static SET_JSON_STR:&'static str = include_str!("small-sets.json");
+pub static SET_JSON_STR:&str = include_str!("small-sets.json");
+pub static PACK_TEMPLATES_STR:&str = include_str!("pack-templates.json");
+/// The bundled sets with their pack templates, collated the same by the page and the command line.
+pub fn builtin_generator() -> Result<SetGenerator, DraftError> {
+    let sets = serde_json::from_str(SET_JSON_STR).map_err(|e| DraftError::BadData(e.to_string()))?;
+    let mut generator = SetGenerator::new(sets)?;
+    generator.set_templates(template::parse_templates(PACK_TEMPLATES_STR).map_err(DraftError::BadData)?);
+    Ok(generator)
+}
 
-const NUM_PACKS:i16 = 18;
-const PICKS_PER_PACK:i16 = 2;
+/// Seconds per pack when the pick timer is first switched on.
+const DEFAULT_PICK_TIMER:u16 = 60;
 
 use yew::prelude::*;
-
+use yew::services::reader::{File, FileData, ReaderService, ReaderTask};
+use yew::services::interval::{IntervalService, IntervalTask};
+use yew::services::keyboard::{KeyboardService, KeyListenerHandle};
+use std::time::Duration;
 use serde_json::{Value, Map};
 
+use deck::Deck;
+use error::DraftError;
+use draft::{Card, Draft, DraftFormat, SetGenerator, SetupInfo};
+use export::ExportFormat;
+use grouping::Grouping;
+use undo::UndoStack;
+use keyboard::KeyAction;
+use stats::PoolStats;
+use pod::PassDirection;
+use ratings::SetRatings;
+use rng::SeededRng;
+use strategy::StrategyKind;
+use storage::SavedDraft;
+
 struct Model {
     link: ComponentLink<Self>,
-    set_generator:SetGenerator,
+    draft:Draft,
     in_draft:bool,
     setup_info:SetupInfo,
-    pack:Pack,
-    sorted_picks: Vec<Vec<Card>>,
-    selected: Vec<CardDisplay>,
-}
-struct SetupInfo {
-    sets:Vec<(String, i16)>
-}
-struct Pack {
-    cards:Vec<Card>,
-    num_picks:i16
-}
-
-#[derive(Clone)]
-struct Card {
-    cmc:i16,
-    name:String,
-    img_url:String,
-    selected:bool
-}
-
-impl std::cmp::PartialEq<Card> for Card {
-    fn eq (&self, other:&Card) -> bool {
-        return self.name == *other.name;
-    }
+    saved_draft:Option<SavedDraft>,
+    // built from the picks once the draft is finished
+    deck:Option<Deck>,
+    custom_sets:Map<String, Value>,
+    reader:ReaderService,
+    reader_task:Option<ReaderTask>,
+    pool_message:Option<String>,
+    // imported ratings tables by set, merged into the draft's
+    set_ratings:SetRatings,
+    // the set the next ratings file is for, when chosen rather than the setup's first
+    ratings_set:Option<String>,
+    ratings_message:Option<String>,
+    sort_by_rating:bool,
+    export_format:ExportFormat,
+    grouping:Grouping,
+    // index into the draft history while the review screen is open
+    review:Option<usize>,
+    // earlier states of the draft, newest last
+    undo_stack:UndoStack<Draft>,
+    // outcome of the last copy or download, shown under the export buttons
+    export_message:Option<Result<String, String>>,
+    selected: Vec<String>,
+    /// Ticks once a second while the pick timer runs.
+    timer_task:Option<IntervalTask>,
+    seconds_left:u16,
+    /// Kept apart from the draft's own rng, so auto-picks don't change the packs a shared seed gives.
+    auto_pick_rng:SeededRng,
+    /// Position in the pack of the card highlighted for keyboard picking.
+    focus:Option<usize>,
+    show_shortcuts:bool,
+    _key_listener:Option<KeyListenerHandle>,
+    /// Shown above every screen until dismissed.
+    error:Option<DraftError>,
 }
 
 enum Msg {
-    Select(CardDisplay),
+    Select(String),
     Confirm(),
     Export(),
+    ExportCopied(Result<(), String>),
+    Download(),
+    ChangeExportFormat(ExportFormat),
+    MoveToSideboard(String),
+    MoveToMain(String),
+    ChangeBasicLand(String, u16),
+    ApplySuggestedLands(),
+    ChangeGrouping(Grouping),
+    Review(Option<usize>),
+    Undo(),
+    ChangeUndoLimit(u16),
+    Tick(),
+    ChangePickTimer(Option<u16>),
+    KeyDown(web_sys::KeyboardEvent),
+    ToggleShortcuts(),
+    ExportHistory(),
     StartDraft(),
+    ResumeDraft(),
+    DiscardSavedDraft(),
+    DismissError(),
+    ChangeSeed(String),
+    ChangeFormat(DraftFormat),
+    ChangeBotStrategy(usize, StrategyKind),
+    LoadPoolFile(File),
+    PoolFileLoaded(FileData),
+    ChangeRatingsSet(String),
+    LoadRatingsFile(File),
+    RatingsFileLoaded(FileData),
+    ToggleSortByRating(),
     AddSet(),
     RemoveSet(String),
     ChangeSet(String,String),
@@ -65,215 +147,174 @@ enum Msg {
     DoNothing()
 }
 
-#[derive(Clone)]
-struct SetGenerator {
-    all_set_json:Map<String, Value>,
-    mythics:Vec<Card>,
-    rares:Vec<Card>,
-    uncommons:Vec<Card>,
-    commons:Vec<Card>,
-    basics:Vec<Card>,
-    pack_number:i16,
-    pack_series:Vec<String>
-}
-
-pub fn shuffle<T>(vec:&mut Vec<T>) {
-    for i in 0..vec.len() {
-        let j = (js_sys::Math::random() * (vec.len() as f64)) as usize;
-        vec.swap(i, j);
+impl Model {
+    /// The setup encoded in the page's URL fragment, keeping only sets we have data for.
+    fn shared_setup(&self) -> Option<(SetupInfo, bool)> {
+        let hash = web_sys::window()?.location().hash().ok()?;
+        let (mut setup_info, start) = share::setup_from_query(&hash)?;
+        let known_sets = self.draft.generator().set_names();
+        let mut seen = vec![];
+        setup_info.sets.retain(|(set, _)| {
+            let keep = known_sets.contains(set) && !seen.contains(set);
+            seen.push(set.clone());
+            keep
+        });
+        if setup_info.sets.is_empty() || draft::num_random_packs(&setup_info.sets, setup_info.format.num_packs) < 0 {
+            return None;
+        }
+        Some((setup_info, start))
     }
-}
 
-#[wasm_bindgen]
-extern "C" {
-    #[wasm_bindgen(js_namespace = console)]
-    pub fn log(s: &str);
-}
+    fn start_draft(&mut self) {
+        if let Err(e) = self.draft.start_setup(&self.setup_info) {
+            // stay on the setup screen, where the setup can be changed
+            self.error = Some(e);
+            return;
+        }
+        self.error = None;
+        self.in_draft = true;
+        self.saved_draft = None;
+        self.deck = None;
+        self.review = None;
+        self.undo_stack = UndoStack::new(self.setup_info.undo_limit as usize);
+        self.reset_timer();
+        self.save();
+        // leave the address bar pointing at this setup, without `start` so a reload offers to resume instead
+        if let Some(window) = web_sys::window() {
+            let _ = window.location().set_hash(&share::setup_to_query(&self.setup_info, false));
+        }
+    }
 
-impl SetGenerator {
-    // pub fn new (all_set_json:Value, init_set:&str) -> Self {
-    //     let mut generator = SetGenerator{all_set_json:all_set_json.as_object().unwrap().clone(), mythics:vec![], rares:vec![], uncommons:vec![], commons:vec![], basics:vec![], pack_number:0, pack_series:vec![]};
-    //     generator.prepare_set(init_set);
-    //     generator
-    // }
-
-    pub fn new (all_set_json:Value) -> Self {
-        let generator = SetGenerator{all_set_json:all_set_json.as_object().unwrap().clone(), mythics:vec![], rares:vec![], uncommons:vec![], commons:vec![], basics:vec![], pack_number:0, pack_series:vec![]};
-        generator
-    }
-
-    fn is_basic(&self, card_name:&str) -> bool {
-        match card_name {
-            "Forest" | "Mountain" | "Swamp" | "Plains" | "Island" => return true, 
-            "Snow-Covered Forest" | "Snow-Covered Mountain" | "Snow-Covered Swamp" | "Snow-Covered Plains" | "Snow-Covered Island" => return true, 
-            _ => return false
-        }
-    }
-    pub fn init_from_setup_packs (&mut self, setup_list:&Vec<(String, i16)>, num_random_packs:i16) {
-        let mut set_list = vec![];
-        // track one of each set for usage in making the random sets
-        let mut all_set_selection_list = vec![];
-        for setup in setup_list {
-            all_set_selection_list.push(setup.0.clone());
-            for _ in 0..setup.1 {
-                set_list.push(setup.0.clone());
-            }
-        }
-        // This insertion could of course be a binary search instead but I don't want to do that
-        for _ in 0..num_random_packs { 
-            let idx = (js_sys::Math::random() * (all_set_selection_list.len() as f64)) as usize;
-            set_list.push(all_set_selection_list[idx].clone());
-        }
-        shuffle(&mut set_list);
-        self.pack_series = set_list;
-        let init_set = self.pack_series[0].clone();
-        self.prepare_set(init_set.as_str());
-    }
-    fn prepare_set(&mut self, set_name:&str) {
-        log(format!("preparing set {}", set_name).as_str());
-        let set_json = self.all_set_json[set_name].as_object().unwrap();
-        self.rares = vec![];
-        self.uncommons = vec![];
-        self.commons = vec![];
-        self.basics = vec![];
-        for card_name in set_json.keys() {
-            let value = set_json[card_name].as_object().unwrap();
-            let cmc = value["c"].as_i64().unwrap();
-            let rarity = value["r"].as_str().unwrap();
-            let id = value["i"].as_i64().unwrap();
-            let url = get_img_url(id);
-            let card :Card= Card{name:String::from(card_name), cmc:cmc as i16, img_url:url, selected:false};
-            if self.is_basic(card_name) {
-                self.basics.push(card)
-            } else {
-                match rarity {
-                    "r" => self.rares.push(card),
-                    "m" => self.mythics.push(card),
-                    "u" => self.uncommons.push(card),
-                    "c" => self.commons.push(card),
-                    _ => {} 
-                }
-            }
+    /// Picks the selected cards and moves on to the next pack.
+    fn confirm_selection(&mut self) {
+        let snapshot = self.draft.snapshot();
+        if let Err(e) = self.draft.pick(&self.selected) {
+            // put back any cards taken before the failure, so the pick can be retried
+            self.draft.resume(snapshot);
+            self.error = Some(e);
+            return;
         }
-        log(format!("{}-m, {}-r, {}-u, {}-c, {}-b", self.mythics.len(), self.rares.len(), self.uncommons.len(), self.commons.len(), self.basics.len()).as_str());
+        self.undo_stack.push(snapshot);
+        self.selected.clear();
+        self.clamp_focus();
+        self.ensure_deck();
+        self.reset_timer();
+        self.save();
     }
 
-    fn pull_card_from_pool(&self, pool:&Vec<Card>) -> Card {
-        let idx = (js_sys::Math::random() * pool.len() as f64) as usize;
-        return pool[idx].clone();
+    /// Whether the human has a pack in front of them, rather than a review or deck building.
+    fn is_picking(&self) -> bool {
+        self.in_draft && self.review.is_none() && self.deck.is_none() && !self.draft.is_finished()
     }
 
-    fn progress_pack(&mut self) {
-        let current_set = self.pack_series[self.pack_number as usize].clone();
-        self.pack_number += 1;
-        if self.pack_number < self.pack_series.len() as i16 {
-            let next_set = self.pack_series[self.pack_number as usize].clone();
-            if current_set!=next_set {
-                self.prepare_set(next_set.as_str());
-            }
-        }
+    /// Keeps the keyboard highlight in place for the next, smaller pack.
+    fn clamp_focus(&mut self) {
+        let len = self.draft.current_pack().cards.len();
+        self.focus = self.focus.filter(|_| len > 0).map(|focus| focus.min(len - 1));
     }
-    fn generate_card(&self, rarity:char) -> Card {
-        match rarity {
-            'r' => {
-                if self.mythics.len()==0 {
-                    return self.pull_card_from_pool(&self.rares)
-                }
-                if js_sys::Math::random() < (1./8.){
-                    // mythics 1 in 8 packs
-                    return self.pull_card_from_pool(&self.mythics)
-                } else {
-                    return self.pull_card_from_pool(&self.rares)
+
+    /// Restarts the pick timer for a new pack, or stops it once there is nothing left to pick.
+    fn reset_timer(&mut self) {
+        match self.setup_info.pick_timer {
+            Some(seconds) if !self.draft.is_finished() => {
+                self.seconds_left = seconds;
+                if self.timer_task.is_none() {
+                    self.timer_task = Some(IntervalService::spawn(Duration::from_secs(1), self.link.callback(|_| Msg::Tick())));
                 }
-            },
-            'u' => self.pull_card_from_pool(&self.uncommons),
-            'c' => self.pull_card_from_pool(&self.commons),
-            'b' => self.pull_card_from_pool(&self.basics),
-            _ => {Card{cmc:1, name:String::from("foo"), img_url:String::from("https://gatherer.wizards.com/Handlers/Image.ashx?multiverseid=235596&type=card"), selected:false}} // fake card
-        }
-    }
-    fn generate_pack(&self) -> Pack {
-        let mut cards = vec![self.generate_card('r')];
-        for _ in 0..3 {
-            let mut card = self.generate_card('u');
-            while cards.contains(&card) {
-                card = self.generate_card('u');
             }
-            cards.push(card.clone());
+            _ => self.timer_task = None
         }
-        for _ in 0..10 {
-            let mut card = self.generate_card('c');
-            while cards.contains(&card) {
-                card = self.generate_card('c');
-            }
-            cards.push(card.clone());
-        }
-        if self.basics.len()>0 {
-            cards.push(self.generate_card('b'));
-        }
-        Pack{cards:cards, num_picks:0}
     }
-}
 
-pub fn get_img_url (multiverse_id:i64) -> String {
-    return format!("https://gatherer.wizards.com/Handlers/Image.ashx?multiverseid={}&type=card", multiverse_id)
-}
+    /// Fills the selection up with the best remaining cards (by rating, then rarity) and picks them.
+    fn auto_pick(&mut self) {
+        let strategy = StrategyKind::HighestRated.strategy();
+        let context = self.draft.pick_context();
+        self.selected = strategy::complete_selection(strategy.as_ref(), self.draft.current_pack(), self.draft.picks(), &self.selected, &context, &mut self.auto_pick_rng);
+        self.confirm_selection();
+    }
 
-impl Model {
-    fn add_sorted_pick(&mut self, card:Card) {
-        while self.sorted_picks.len() <= card.cmc as usize {
-            self.sorted_picks.push(vec![]);
-        }
-        let mut idx = 0;
-        for existing in &self.sorted_picks[card.cmc as usize] {
-            if existing.name < card.name {
-                idx+=1;
-            } else {
-                break;
-            }
+    fn save(&self) {
+        storage::save_draft(&self.setup_info, &self.draft, self.deck.as_ref());
+    }
+
+    /// Starts deck building with every pick in the main deck, once the last pick is made.
+    fn ensure_deck(&mut self) {
+        if self.deck.is_none() && self.draft.is_finished() {
+            self.deck = Some(Deck::from_picks(self.draft.picks().to_vec()));
         }
-        if idx < self.sorted_picks[card.cmc as usize].len() {
-            self.sorted_picks[card.cmc as usize].insert(idx, card);
-        } else {
-            self.sorted_picks[card.cmc as usize].push(card);
+    }
+
+    fn maybe_resume_buttons(&self) -> Html {
+        match &self.saved_draft {
+            Some(saved) => html!{
+                <div class="container my-3">
+                    <div class="col-md-12 text-center">
+                        <button type="button" class="btn btn-primary" onclick=self.link.callback(|_| Msg::ResumeDraft())>
+                            {if saved.draft.is_finished() {
+                                String::from("Resume deck building")
+                            } else {
+                                format!("Resume draft (pack {})", (saved.draft.pack_number() + 1).min(saved.draft.format().num_packs))
+                            }}
+                        </button>
+                        {" "}
+                        <button type="button" class="btn btn-outline-secondary" onclick=self.link.callback(|_| Msg::DiscardSavedDraft())>{"Discard"}</button>
+                        <p class="text-muted small mt-2">{"Starting the draft set up below replaces the saved one."}</p>
+                    </div>
+                </div>
+            },
+            None => html!{}
         }
     }
 
-    fn pick_card(&mut self, name:String) {
-        for card_idx in 0..self.pack.cards.len() {
-            let card = self.pack.cards[card_idx].clone();
-            if card.name == name {
-                self.add_sorted_pick(card);
-                self.pack.cards.remove(card_idx);
-                self.pack.num_picks += 1;
-                if self.pack.num_picks>=PICKS_PER_PACK {
-                    self.generate_next_pack();
-                }
-                break;
-            }
+    fn maybe_undo_button(&self) -> Html {
+        if !self.undo_stack.is_enabled() {
+            return html!{};
+        }
+        html!{
+            <button type="button" class="btn btn-outline-secondary ml-2" disabled={self.undo_stack.is_empty()} onclick=self.link.callback(|_| Msg::Undo())>
+                {format!("Undo ({})", self.undo_stack.len())}
+            </button>
         }
     }
 
-    fn generate_next_pack (&mut self) { 
-        self.set_generator.progress_pack();
-        if self.set_generator.pack_number < NUM_PACKS {
-            self.pack = self.set_generator.generate_pack();
-        } else {
-            self.pack = Pack{cards:vec![], num_picks:0};
+    fn pass_note(&self) -> String {
+        if !self.draft.format().is_pod() {
+            return String::new();
+        }
+        match self.draft.pass_direction() {
+            PassDirection::Left => String::from("(passing left)"),
+            PassDirection::Right => String::from("(passing right)")
         }
     }
 
     fn produce_pack_header(&self) -> Html {
-        if self.set_generator.pack_number >= NUM_PACKS{
+        if self.draft.is_finished() {
             html!{}
         } else {
             html!{
                 <>
-                <h2> {"Pack "} {self.set_generator.pack_number + 1} </h2>
+                <h2> {"Pack "} {self.draft.pack_number() + 1} {self.pass_note()} <small class="text-muted">{" Seed "} {self.setup_info.seed}</small></h2>
+                {for self.draft.current_pack().short_slots.iter().map(|short| html!{
+                    <div class="alert alert-warning py-1 mx-2 mb-1">{format!("Short pack: {}", short)}</div>
+                })}
 
                 <div class="container my-3 bg-light">
                     <div class="col-md-12 text-center">
-                        <button type="button" disabled={self.selected.len()<PICKS_PER_PACK as usize} class="btn btn-primary" onclick=self.link.callback(|_| Msg::Confirm())>{"Choose"}</button>
+                        <button type="button" disabled={self.selected.len()<self.draft.picks_required()} class="btn btn-primary" onclick=self.link.callback(|_| Msg::Confirm())>{"Choose"}</button>
+                        {self.maybe_undo_button()}
+                        <button type="button" class="btn btn-outline-secondary ml-2" title="Keyboard shortcuts" onclick=self.link.callback(|_| Msg::ToggleShortcuts())>{"?"}</button>
+                        {if self.timer_task.is_some() {html!{
+                            <span class={format!("badge ml-3 {}", if self.seconds_left <= 5 {"badge-danger"} else {"badge-secondary"})}>
+                                {format!("{}s", self.seconds_left)}
+                            </span>
+                        }} else {html!{}}}
+                        {if self.draft.ratings().is_empty() {html!{}} else {html!{
+                            <label class="ml-3">
+                                <input type="checkbox" checked=self.sort_by_rating onclick=self.link.callback(|_| Msg::ToggleSortByRating())/>
+                                {" Sort pack by rating"}
+                            </label>
+                        }}}
                     </div>
                 </div>
                 </>
@@ -281,15 +322,50 @@ impl Model {
         }
     }
 
+    /// Name for exported decks, from the sets drafted.
+    fn deck_name(&self) -> String {
+        format!("{} draft", self.drafted_sets().join(" "))
+    }
+
+    fn drafted_sets(&self) -> Vec<&str> {
+        self.setup_info.sets.iter().filter(|(_, num_packs)| *num_packs > 0).map(|(set, _)| set.as_str()).collect()
+    }
+
+    fn export_text(&self) -> String {
+        match &self.deck {
+            Some(deck) => export::export_deck(self.export_format, &self.deck_name(), &deck.main_with_lands(), &deck.sideboard),
+            None => export::export_deck(self.export_format, &self.deck_name(), self.draft.picks(), &[])
+        }
+    }
+
     fn maybe_export_button(&self) -> Html {
-        if self.set_generator.pack_number < NUM_PACKS{
+        if !self.draft.is_finished() {
             html!{}
         } else {
             html!{
                 <>
                 <div class="container my-3 bg-light">
                     <div class="col-md-12 text-center">
+                        <select class="mr-2" onchange=self.link.callback(|e| {
+                            match e {
+                                yew::html::ChangeData::Select(el) => match ExportFormat::from_code(&el.value()) {
+                                    Some(format) => Msg::ChangeExportFormat(format),
+                                    None => Msg::DoNothing()
+                                },
+                                _ => Msg::DoNothing()
+                            }
+                        })>
+                        {for ExportFormat::ALL.iter().map(|format| html!{
+                            <option value=format.code() selected={*format == self.export_format}>{format.name()}</option>
+                        })}
+                        </select>
                         <button type="button" class="btn btn-success" onclick=self.link.callback(|_| Msg::Export())>{"Export to Clipboard"}</button>
+                        <button type="button" class="btn btn-outline-success ml-2" onclick=self.link.callback(|_| Msg::Download())>{"Download"}</button>
+                        {match &self.export_message {
+                            Some(Ok(message)) => html!{<p class="text-success">{message}</p>},
+                            Some(Err(message)) => html!{<p class="text-danger">{message}</p>},
+                            None => html!{}
+                        }}
                     </div>
                 </div>
                 </>
@@ -297,83 +373,451 @@ impl Model {
         }
     }
 
+    /// The current pack in the order it is shown, best rated first when sorting by rating.
+    fn pack_cards(&self) -> Vec<&Card> {
+        let mut cards:Vec<&Card> = self.draft.current_pack().cards.iter().collect();
+        if self.sort_by_rating {
+            self.draft.ratings().sort_cards(&mut cards);
+        }
+        cards
+    }
+
+    /// Hands the draft every imported table at once, so bots and the display see the same scores.
+    fn apply_ratings(&mut self) {
+        self.draft.set_ratings(self.set_ratings.clone());
+    }
+
+    /// Sets a ratings file can be loaded for: those in the setup, or every set before any is chosen.
+    fn ratings_sets(&self) -> Vec<String> {
+        let mut sets = vec![];
+        for (set, _) in &self.setup_info.sets {
+            if !sets.contains(set) {
+                sets.push(set.clone());
+            }
+        }
+        if sets.is_empty() {
+            sets = self.draft.generator().set_names();
+        }
+        sets
+    }
+
+    /// The set the next ratings file is for, following the setup unless another was chosen.
+    fn ratings_set(&self) -> String {
+        let sets = self.ratings_sets();
+        self.ratings_set.clone().filter(|set| sets.contains(set)).or_else(|| sets.first().cloned()).unwrap_or_default()
+    }
+
+    fn shortcut_help(&self) -> Html {
+        if !self.show_shortcuts {
+            return html!{};
+        }
+        html!{
+            <div class="card shadow shortcut-help">
+                <div class="card-body">
+                    <h5 class="card-title">{"Keyboard shortcuts"}</h5>
+                    <table class="table table-sm mb-2">
+                        <tbody>
+                            {for keyboard::SHORTCUTS.iter().map(|(keys, action)| html!{
+                                <tr><td><kbd>{keys}</kbd></td><td>{action}</td></tr>
+                            })}
+                        </tbody>
+                    </table>
+                    <button type="button" class="btn btn-sm btn-outline-secondary" onclick=self.link.callback(|_| Msg::ToggleShortcuts())>{"Close"}</button>
+                </div>
+            </div>
+        }
+    }
+
     fn draft_screen(&self) -> Html {
         html! {
             <>
             {self.produce_pack_header()}
-            <div class="d-flex flex-row flex-wrap px-2 mt-2 bg-light">
+            {self.shortcut_help()}
+            <div id="pack-grid" class="d-flex flex-row flex-wrap px-2 mt-2 bg-light">
                 // <button onclick=self.link.callback(|_| Msg::Add(1))>{ "+1" }</button>
                 // <button onclick=self.link.callback(|_| Msg::Add(2))>{ "+2" }</button>
                 { 
-                    for self.pack.cards.iter().map( |e| html!{
-                        <CardDisplay  onsignal=self.link.callback(|display| Msg::Select(display)) name=&e.name url=&e.img_url selected=&e.selected/>
+                    for self.pack_cards().into_iter().enumerate().map( |(idx, e)| html!{
+                        <CardDisplay  onsignal=self.link.callback(|display:CardDisplay| Msg::Select(display.name)) name=&e.name url=&e.img_url selected=self.selected.contains(&e.name) wheeled=self.draft.wheeled_cards().contains(&e) rating=self.draft.ratings().get(&e) focused={self.focus == Some(idx)}/>
                     })
                 }
             </div>
             <hr/>
             <h2> {"Deck: "} </h2>
+            {self.stats_panel(&PoolStats::from_cards(self.draft.picks()), false)}
             {self.maybe_export_button()}
+            {self.grouping_select(self.draft.picks())}
+            {self.pool_view(self.draft.picks(), None)}
+            </>
+        }
+    }
+
+    /// Curve, pips, card types and suggested basics; `can_apply` offers to set the land station to the suggestion.
+    fn stats_panel(&self, stats:&PoolStats, can_apply:bool) -> Html {
+        let tallest = stats.curve.iter().cloned().max().unwrap_or(0).max(1);
+        let suggested = stats.suggested_lands(stats::DEFAULT_LANDS.saturating_sub(stats.lands));
+        let land_names:Vec<String> = suggested.iter()
+            .map(|(color, lands)| format!("{} {}", lands, deck::BASIC_LANDS.iter().find(|(_, land_color)| land_color == color).map(|(land, _)| *land).unwrap_or("")))
+            .collect();
+        html!{
+            <div class="container my-3 d-flex flex-row flex-wrap justify-content-center stats-panel">
+                <div class="mr-4">
+                    <div class="d-flex flex-row align-items-end curve">
+                        {for stats.curve.iter().enumerate().map(|(cmc, count)| html!{
+                            <div class="text-center mx-1">
+                                <small>{count}</small>
+                                <div class="curve-bar bg-primary" style=format!("height:{}px", count * 80 / tallest)></div>
+                                <small class="text-muted">{cmc}</small>
+                            </div>
+                        })}
+                    </div>
+                    <small class="text-muted">{"Mana curve"}</small>
+                </div>
+                <div class="mr-4">
+                    <div>{for "WUBRG".chars().zip(stats.pips.iter()).filter(|(_, pips)| **pips > 0).map(|(color, pips)| html!{
+                        <span class=format!("badge mana-{} mr-1", color.to_ascii_lowercase())>{format!("{} {}", color, pips)}</span>
+                    })}</div>
+                    <div>
+                        {format!("{} creatures, {} non-creatures, {} lands", stats.creatures, stats.non_creatures, stats.lands)}
+                        {if stats.unknown > 0 {format!(", {} of unknown type", stats.unknown)} else {String::new()}}
+                    </div>
+                    {if land_names.is_empty() {html!{}} else {html!{
+                        <div>
+                            {format!("Suggested basics: {}", land_names.join(", "))}
+                            {if can_apply {html!{
+                                <button type="button" class="btn btn-sm btn-outline-primary ml-2" onclick=self.link.callback(|_| Msg::ApplySuggestedLands())>{"Use"}</button>
+                            }} else {html!{}}}
+                        </div>
+                    }}}
+                </div>
+            </div>
+        }
+    }
+
+    /// The grouping picker, with a note when `cards` lack the details color and type views need.
+    fn grouping_select(&self, cards:&[Card]) -> Html {
+        let lacks_details = !cards.is_empty() && !cards.iter().any(Card::has_details);
+        html!{
+            <div class="container my-2">
+                <div class="col-md-12 text-center">
+                    <label for="grouping">{"Group by: "}</label>
+                    <select id="grouping" onchange=self.link.callback(|e| {
+                        match e {
+                            yew::html::ChangeData::Select(el) => match Grouping::from_code(&el.value()) {
+                                Some(grouping) => Msg::ChangeGrouping(grouping),
+                                None => Msg::DoNothing()
+                            },
+                            _ => Msg::DoNothing()
+                        }
+                    })>
+                    {for Grouping::ALL.iter().map(|grouping| html!{
+                        <option value=grouping.code() selected={*grouping == self.grouping}>{grouping.name()}</option>
+                    })}
+                    </select>
+                    {if lacks_details {html!{
+                        <p class="text-muted small">{"The built-in sets only give mana value and rarity, so colors, types and pips show for uploaded sets with `ci`, `mc` and `t` fields."}</p>
+                    }} else {html!{}}}
+                </div>
+            </div>
+        }
+    }
+
+    /// Cards laid out in columns by the chosen grouping; clicking a card sends `on_click` with its name, if given.
+    fn pool_view(&self, cards:&[Card], on_click:Option<fn(String) -> Msg>) -> Html {
+        html!{
             <div class="d-flex container-fluid deck-viewer bg-light">
                 <div class="row px-2 mt-2 flipped">
-                
-                    // <button onclick=self.link.callback(|_| Msg::Add(1))>{ "+1" }</button>
-                    // <button onclick=self.link.callback(|_| Msg::Add(2))>{ "+2" }</button>
-                    { 
-                        for self.sorted_picks.iter().map(|pick_column| html!{
-                         
-                            <div class="col-xs-4">
-                            { 
-                                for pick_column.iter().map(|e| html!{
+                    {for grouping::group_cards(cards, self.grouping).into_iter().map(|(label, column)| html!{
+                        <div class="col-xs-4">
+                        <small class="d-block text-center text-muted">{label}</small>
+                        {for column.into_iter().map(|card| {
+                            let name = card.name.clone();
+                            match on_click {
+                                Some(on_click) => html!{
                                     <div class="picked-card-container">
-                                        <img class="picked-card shadow-sm mx-1 mt-1 mb-1" src=e.img_url alt=e.name/>
+                                        <img class="picked-card shadow-sm mx-1 mt-1 mb-1 movable-card" src=&card.img_url alt=&card.name onclick=self.link.callback(move |_| on_click(name.clone()))/>
                                     </div>
-                                })
+                                },
+                                None => html!{
+                                    <div class="picked-card-container">
+                                        <img class="picked-card shadow-sm mx-1 mt-1 mb-1" src=&card.img_url alt=&card.name/>
+                                    </div>
+                                }
                             }
-                            </div>
-                        })
-                    }
+                        })}
+                        </div>
+                    })}
+                </div>
+            </div>
+        }
+    }
+
+    /// Steps through the packs of a finished draft, highlighting what was taken from each.
+    fn review_screen(&self, idx:usize) -> Html {
+        let history = self.draft.history();
+        let record = match history.get(idx) {
+            Some(record) => record,
+            None => return html!{}
+        };
+        let time = match record.timestamp {
+            Some(timestamp) => String::from(js_sys::Date::new(&JsValue::from_f64(timestamp)).to_locale_time_string("default")),
+            None => String::new()
+        };
+        html!{
+            <>
+            <h2>
+                {format!("Review: pack {} ({}), {} of {} ", record.pack_number + 1, record.set, idx + 1, history.len())}
+                <small class="text-muted">{time}</small>
+            </h2>
+            <div class="container my-3 bg-light">
+                <div class="col-md-12 text-center">
+                    <button type="button" class="btn btn-outline-primary" disabled={idx == 0} onclick=self.link.callback(move |_| Msg::Review(Some(idx - 1)))>{"Previous"}</button>
+                    <button type="button" class="btn btn-outline-primary ml-2" disabled={idx + 1 >= history.len()} onclick=self.link.callback(move |_| Msg::Review(Some(idx + 1)))>{"Next"}</button>
+                    <button type="button" class="btn btn-outline-secondary ml-2" onclick=self.link.callback(|_| Msg::Review(None))>{"Back to deck"}</button>
+                    <button type="button" class="btn btn-outline-success ml-2" onclick=self.link.callback(|_| Msg::ExportHistory())>{"Export history (JSON)"}</button>
+                    {match &self.export_message {
+                        Some(Ok(message)) => html!{<p class="text-success">{message}</p>},
+                        Some(Err(message)) => html!{<p class="text-danger">{message}</p>},
+                        None => html!{}
+                    }}
                 </div>
             </div>
+            <p class="text-center">{format!("Took {}", record.picked.join(", "))}</p>
+            <div class="d-flex flex-row flex-wrap px-2 mt-2 bg-light">
+                {for record.contents.iter().map(|card| html!{
+                    <CardDisplay onsignal=self.link.callback(|_| Msg::DoNothing()) name=&card.name url=&card.img_url selected=record.picked.contains(&card.name) rating=self.draft.ratings().get(&card)/>
+                })}
+            </div>
+            </>
+        }
+    }
+
+    fn land_station(&self, deck:&Deck) -> Html {
+        html!{
+            <div class="container my-3">
+                <div class="col-md-12 text-center">
+                    <b>{"Basic lands: "}</b>
+                    {for deck::BASIC_LANDS.iter().map(|(land, _)| {
+                        let count = deck.basic_land_count(land);
+                        html!{
+                            <span class="mr-3">
+                                {format!("{} ", land)}
+                                <button type="button" class="btn btn-sm btn-outline-secondary" disabled={count == 0} onclick=self.link.callback(move |_| Msg::ChangeBasicLand(String::from(*land), count.saturating_sub(1)))>{"−"}</button>
+                                {format!(" {} ", count)}
+                                <button type="button" class="btn btn-sm btn-outline-secondary" onclick=self.link.callback(move |_| Msg::ChangeBasicLand(String::from(*land), count + 1))>{"+"}</button>
+                            </span>
+                        }
+                    })}
+                </div>
+            </div>
+        }
+    }
+
+    fn deck_screen(&self, deck:&Deck) -> Html {
+        let main_size = deck.main_size();
+        html!{
+            <>
+            <h2>
+                {"Deck building "}
+                <small class={if main_size < deck::MIN_DECK_SIZE {"text-danger"} else {"text-muted"}}>
+                    {format!("Main deck: {} cards, sideboard: {}", main_size, deck.sideboard.len())}
+                </small>
+            </h2>
+            <div class="container my-2">
+                <div class="col-md-12 text-center">
+                    <button type="button" class="btn btn-outline-primary" disabled={self.draft.history().is_empty()} onclick=self.link.callback(|_| Msg::Review(Some(0)))>{"Review picks"}</button>
+                    {self.maybe_undo_button()}
+                </div>
+            </div>
+            {self.stats_panel(&PoolStats::from_cards(&deck.main), true)}
+            {self.land_station(deck)}
+            {self.maybe_export_button()}
+            <p class="text-muted text-center">{"Click a card to move it between the main deck and the sideboard."}</p>
+            {self.grouping_select(&deck.main)}
+            {self.pool_view(&deck.main, Some(Msg::MoveToSideboard))}
+            <h2>{"Sideboard"}</h2>
+            <div class="d-flex flex-row flex-wrap px-2 mt-2 bg-light">
+                {for deck.sideboard.iter().map(|card| {
+                    let name = card.name.clone();
+                    html!{
+                        <img class="sideboard-card shadow-sm mx-1 mt-1 mb-1 movable-card" src=&card.img_url alt=&card.name onclick=self.link.callback(move |_| Msg::MoveToMain(name.clone()))/>
+                    }
+                })}
+            </div>
             </>
         }
     }
 
     fn get_random_set_num (&self) -> i16 {
-        return NUM_PACKS-self.get_unassigned_packs_num()
+        draft::num_random_packs(&self.setup_info.sets, self.setup_info.format.num_packs)
     }
 
-    fn get_unassigned_packs_num (&self) -> i16 {
-        let mut sum = 0;
-        for set in &self.setup_info.sets {
-            sum+=set.1
+    fn format_controls(&self) -> Html {
+        let format = self.setup_info.format;
+        let presets = DraftFormat::presets();
+        let is_custom = !presets.iter().any(|(_, preset)| *preset == format);
+        let parse_positive = |value:&str| value.trim().parse::<i16>().ok().filter(|value| *value > 0);
+        html!{
+            <div class="container my-3">
+                <div class="col-md-12 text-center">
+                    <label for="format">{"Format: "}</label>
+                    <select id="format" onchange=self.link.callback(move |e| {
+                        match e {
+                            yew::html::ChangeData::Select(el) => {
+                                match el.value().parse::<usize>().ok().and_then(|idx| DraftFormat::presets().get(idx).map(|preset| preset.1)) {
+                                    Some(preset) => Msg::ChangeFormat(preset),
+                                    None => Msg::DoNothing()
+                                }
+                            }
+                            _ => Msg::DoNothing()
+                        }
+                    })>
+                    {for presets.iter().enumerate().map(|(idx, (name, preset))| html!{
+                        <option value=idx.to_string() selected={*preset == format}>{name}</option>
+                    })}
+                    <option value="custom" selected=is_custom disabled=true>{"Custom"}</option>
+                    </select>
+                    {" Packs: "}
+                    <input type="number" min="1" class="number-input" value=format.num_packs.to_string() oninput=self.link.callback(move |e:InputData| {
+                        match parse_positive(&e.value) {
+                            Some(num_packs) => Msg::ChangeFormat(DraftFormat{num_packs, ..format}),
+                            None => Msg::DoNothing()
+                        }
+                    })/>
+                    {" Picks per pack: "}
+                    <input type="number" min="1" class="number-input" value=format.picks_per_pack.to_string() oninput=self.link.callback(move |e:InputData| {
+                        match parse_positive(&e.value) {
+                            Some(picks_per_pack) => Msg::ChangeFormat(DraftFormat{picks_per_pack, ..format}),
+                            None => Msg::DoNothing()
+                        }
+                    })/>
+                    {" Seats: "}
+                    <input type="number" min="1" max="16" class="number-input" value=format.seats.to_string() oninput=self.link.callback(move |e:InputData| {
+                        match parse_positive(&e.value) {
+                            Some(seats) if seats <= 16 => Msg::ChangeFormat(DraftFormat{seats:seats as u8, ..format}),
+                            _ => Msg::DoNothing()
+                        }
+                    })/>
+                    {" Pack size: "}
+                    <input type="number" min="1" class="number-input" placeholder="set default" value=format.pack_size.map(|size| size.to_string()).unwrap_or_default() oninput=self.link.callback(move |e:InputData| {
+                        if e.value.trim().is_empty() {
+                            Msg::ChangeFormat(DraftFormat{pack_size:None, ..format})
+                        } else {
+                            match parse_positive(&e.value) {
+                                Some(pack_size) => Msg::ChangeFormat(DraftFormat{pack_size:Some(pack_size as u16), ..format}),
+                                None => Msg::DoNothing()
+                            }
+                        }
+                    })/>
+                </div>
+                {self.bot_strategy_controls()}
+            </div>
+        }
+    }
+
+    fn bot_strategy_controls(&self) -> Html {
+        let num_bots = self.setup_info.format.seats.saturating_sub(1) as usize;
+        if num_bots == 0 {
+            return html!{};
+        }
+        html!{
+            <div class="col-md-12 text-center mt-2">
+                {for (0..num_bots).map(|seat| {
+                    let current = self.setup_info.bot_strategies.get(seat).cloned().unwrap_or_default();
+                    html!{
+                        <span class="mr-2">
+                            {format!("Bot {}: ", seat + 1)}
+                            <select onchange=self.link.callback(move |e| {
+                                match e {
+                                    yew::html::ChangeData::Select(el) => {
+                                        match StrategyKind::from_code(&el.value()) {
+                                            Some(strategy) => Msg::ChangeBotStrategy(seat, strategy),
+                                            None => Msg::DoNothing()
+                                        }
+                                    }
+                                    _ => Msg::DoNothing()
+                                }
+                            })>
+                            {for StrategyKind::ALL.iter().map(|strategy| html!{
+                                <option value=strategy.code() selected={*strategy == current}>{strategy.name()}</option>
+                            })}
+                            </select>
+                        </span>
+                    }
+                })}
+            </div>
         }
-        return sum
     }
 
     fn get_unused_sets (&self, ignored_used_set:String) -> Vec<String>{
         let mut unused_sets = vec![];
-        for set in self.set_generator.all_set_json.keys() {
-            if set == &ignored_used_set {
+        for set in self.draft.generator().set_names() {
+            if set == ignored_used_set {
                 unused_sets.push(ignored_used_set.clone());
                 continue;
             }
             let mut used = false;
             for used_set in &self.setup_info.sets {
-                if used_set.0 == *set {
+                if used_set.0 == set {
                     used = true;
                     break;
                 }
             }
             if !used {
-                unused_sets.push(set.clone());
+                unused_sets.push(set);
             }
         }
-        return unused_sets;
+        unused_sets
+    }
+
+    fn ratings_controls(&self) -> Html {
+        let rated_sets:Vec<String> = self.set_ratings.tables().iter().map(|(set, ratings)| format!("{} ({})", set, ratings.len())).collect();
+        let ratings_set = self.ratings_set();
+        html!{
+            <div class="container my-3">
+                <div class="col-md-12 text-center">
+                    <label for="ratings-file">{"Ratings for "}</label>
+                    <select onchange=self.link.callback(|e| {
+                        match e {
+                            yew::html::ChangeData::Select(el) => Msg::ChangeRatingsSet(el.value()),
+                            _ => Msg::DoNothing()
+                        }
+                    })>
+                    {for self.ratings_sets().iter().map(|set| html!{
+                        <option value=set.clone() selected={*set == ratings_set}>{set}</option>
+                    })}
+                    </select>
+                    {": "}
+                    <input id="ratings-file" type="file" accept=".csv,.json" onchange=self.link.callback(|e| {
+                        match e {
+                            yew::html::ChangeData::Files(files) => {
+                                match files.get(0) {
+                                    Some(file) => Msg::LoadRatingsFile(file),
+                                    None => Msg::DoNothing()
+                                }
+                            }
+                            _ => Msg::DoNothing()
+                        }
+                    })/>
+                    {if rated_sets.is_empty() {html!{}} else {html!{
+                        <p class="text-muted">{format!("Rated: {}", rated_sets.join(", "))}</p>
+                    }}}
+                    {match &self.ratings_message {
+                        Some(message) => html!{<p class="text-muted">{message}</p>},
+                        None => html!{}
+                    }}
+                </div>
+            </div>
+        }
     }
 
     fn setup_screen(&self) -> Html {
+        let undo_limit = self.setup_info.undo_limit;
+        let pick_timer = self.setup_info.pick_timer;
         html!{
             <>
+            {self.maybe_resume_buttons()}
+            {self.format_controls()}
             <table class="table table-nonfluid table-bordered table-striped text-center">
                 <thead>
                     <tr>
@@ -391,7 +835,7 @@ impl Model {
                         let set_name = setup_set.0.clone(); 
                         let num_packs = setup_set.clone().1; 
                         let num_packs_clone = setup_set.clone().1; 
-                        let unassigned_packs = NUM_PACKS - self.get_unassigned_packs_num(); 
+                        let unassigned_packs = self.get_random_set_num();
                         html!{
                         <tr>
                             <td class="pt-3-half">  
@@ -462,6 +906,74 @@ impl Model {
                     }else{html!{}}}
                 </tbody>
             </table>
+            <div class="container my-3">
+                <div class="col-md-12 text-center">
+                    <label for="seed">{"Seed: "}</label>
+                    <input id="seed" type="text" value=self.setup_info.seed.to_string() oninput=self.link.callback(|e:InputData| Msg::ChangeSeed(e.value))/>
+                    {" "}
+                    <a href=format!("#{}", share::setup_to_query(&self.setup_info, true))>{"Link to this draft"}</a>
+                </div>
+                <div class="col-md-12 text-center mt-2">
+                    <label title="Undo rewinds the random state too, so a pick always opens the same next pack">
+                        <input type="checkbox" checked={self.setup_info.undo_limit > 0} onclick=self.link.callback(move |_| Msg::ChangeUndoLimit(if undo_limit > 0 {0} else {undo::DEFAULT_UNDO_LIMIT}))/>
+                        {" Allow undo"}
+                    </label>
+                    {if undo_limit > 0 {html!{
+                        <>
+                        {", up to "}
+                        <input type="number" min="1" class="number-input" value=undo_limit.to_string() oninput=self.link.callback(|e:InputData| {
+                            match e.value.trim().parse::<u16>() {
+                                Ok(limit) if limit > 0 => Msg::ChangeUndoLimit(limit),
+                                _ => Msg::DoNothing()
+                            }
+                        })/>
+                        {" picks"}
+                        </>
+                    }} else {html!{}}}
+                </div>
+                <div class="col-md-12 text-center mt-2">
+                    <label>
+                        <input type="checkbox" checked={pick_timer.is_some()} onclick=self.link.callback(move |_| Msg::ChangePickTimer(if pick_timer.is_some() {None} else {Some(DEFAULT_PICK_TIMER)}))/>
+                        {" Pick timer"}
+                    </label>
+                    {match pick_timer {
+                        Some(seconds) => html!{
+                            <>
+                            {", "}
+                            <input type="number" min="1" class="number-input" value=seconds.to_string() oninput=self.link.callback(|e:InputData| {
+                                match e.value.trim().parse::<u16>() {
+                                    Ok(seconds) if seconds > 0 => Msg::ChangePickTimer(Some(seconds)),
+                                    _ => Msg::DoNothing()
+                                }
+                            })/>
+                            {" seconds per pack"}
+                            </>
+                        },
+                        None => html!{}
+                    }}
+                </div>
+            </div>
+            <div class="container my-3">
+                <div class="col-md-12 text-center">
+                    <label for="pool-file">{"Add a custom pool or cube list: "}</label>
+                    <input id="pool-file" type="file" accept=".json,.txt" onchange=self.link.callback(|e| {
+                        match e {
+                            yew::html::ChangeData::Files(files) => {
+                                match files.get(0) {
+                                    Some(file) => Msg::LoadPoolFile(file),
+                                    None => Msg::DoNothing()
+                                }
+                            }
+                            _ => Msg::DoNothing()
+                        }
+                    })/>
+                    {match &self.pool_message {
+                        Some(message) => html!{<p class="text-muted">{message}</p>},
+                        None => html!{}
+                    }}
+                </div>
+            </div>
+            {self.ratings_controls()}
             <div class="container my-3">
                 <div class="col-md-12 text-center">
                     <button type="button" class="btn btn-success" onclick=self.link.callback(|_| Msg::StartDraft())>{"Start"}</button>
@@ -477,75 +989,342 @@ impl Component for Model {
     type Properties = ();
     fn create(_: Self::Properties, link: ComponentLink<Self>) -> Self {
         println!("preparse");
-        let generator =  SetGenerator::new(serde_json::from_str(SET_JSON_STR).unwrap());
-        Self {
+        let (mut generator, error) = match builtin_generator() {
+            Ok(generator) => (generator, None),
+            Err(e) => (SetGenerator::default(), Some(e))
+        };
+        let custom_sets = storage::load_custom_sets();
+        generator.add_sets(custom_sets.clone());
+        // keep fresh seeds short enough to read out to another player
+        let seed = (js_sys::Math::random() * u32::MAX as f64) as u64;
+        let key_listener = web_sys::window().and_then(|window| window.document())
+            .map(|document| KeyboardService::register_key_down(&document, link.callback(Msg::KeyDown)));
+        let mut model = Self {
             link,
-            set_generator:generator.clone(),
-            setup_info:SetupInfo{sets:vec![(String::from("MH1"),18)]},
+            draft:Draft::new(generator, SeededRng::new(seed)),
+            setup_info:SetupInfo{sets:vec![(String::from("MH1"),18)], seed, format:DraftFormat::default(), bot_strategies:vec![], undo_limit:undo::DEFAULT_UNDO_LIMIT, pick_timer:None},
             in_draft:false,
-            pack:Pack{cards:vec![], num_picks:0},
-            sorted_picks: vec![],
-            selected:vec![]
+            saved_draft:storage::load_draft(),
+            deck:None,
+            custom_sets,
+            reader:ReaderService::new(),
+            reader_task:None,
+            pool_message:None,
+            set_ratings:storage::load_ratings(),
+            ratings_set:None,
+            ratings_message:None,
+            sort_by_rating:false,
+            export_format:ExportFormat::default(),
+            grouping:Grouping::default(),
+            review:None,
+            undo_stack:UndoStack::new(undo::DEFAULT_UNDO_LIMIT as usize),
+            export_message:None,
+            selected:vec![],
+            timer_task:None,
+            seconds_left:0,
+            auto_pick_rng:SeededRng::new((js_sys::Math::random() * u32::MAX as f64) as u64),
+            focus:None,
+            show_shortcuts:false,
+            _key_listener:key_listener,
+            error
+        };
+        model.draft.set_clock(js_sys::Date::now);
+        model.apply_ratings();
+        if let Some((setup_info, start)) = model.shared_setup() {
+            model.setup_info = setup_info;
+            // a saved draft is never replaced unasked; the shared setup waits on the setup screen instead
+            if start && model.saved_draft.is_none() {
+                model.start_draft();
+            }
         }
+        model
     }
 
     fn update(&mut self, msg: Self::Message) -> ShouldRender {
         match msg {
-            Msg::Select(display) => {
-                if self.selected.len()>=PICKS_PER_PACK as usize {
-                    let oldest_selected = self.selected.first().unwrap();
-                    if self.selected.contains(&display) {
-                        // card already selected, do nothing
-                    } else {
-                        // a different card was selected
-                        for card_idx in 0..self.pack.cards.len() {
-                            let card = &mut self.pack.cards[card_idx];
-                            if card.name == display.name {
-                                card.selected = true;
-                            }
-                            if card.name == oldest_selected.name {
-                                card.selected = false;
-                            }
-                        }
+            Msg::Select(name) => {
+                if self.draft.picks_required() == 0 {
+                    return false;
+                }
+                if let Some(idx) = self.selected.iter().position(|selected| *selected == name) {
+                    self.selected.remove(idx);
+                } else {
+                    if self.selected.len()>=self.draft.picks_required() {
+                        // a different card was selected, so the oldest selection is dropped
                         self.selected.remove(0);
-                        self.selected.push(display);
                     }
-                } else {
-                    for card_idx in 0..self.pack.cards.len() {
-                        let card = &mut self.pack.cards[card_idx];
-                        if card.name == display.name {
-                            card.selected = true;
+                    self.selected.push(name);
+                }
+            }
+            Msg::Confirm() => {
+                self.confirm_selection();
+            }
+            Msg::Export() => {
+                self.export_message = None;
+                browser::copy_text(&self.export_text(), self.link.callback(Msg::ExportCopied));
+            }
+            Msg::ExportCopied(result) => {
+                self.export_message = Some(match result {
+                    Ok(()) => Ok(String::from("Copied to the clipboard")),
+                    Err(e) => Err(format!("Could not copy to the clipboard ({}); try Download instead", e))
+                });
+            }
+            Msg::Download() => {
+                let date:String = String::from(js_sys::Date::new_0().to_iso_string()).chars().take(10).collect();
+                let file_name = export::export_file_name(&self.drafted_sets(), &date, self.export_format);
+                self.export_message = Some(match browser::download_text(&file_name, &self.export_text()) {
+                    Ok(()) => Ok(format!("Saved {}", file_name)),
+                    Err(e) => Err(format!("Could not save {}: {}", file_name, e.as_string().unwrap_or_default()))
+                });
+            }
+            Msg::ChangeExportFormat(format) => {
+                self.export_format = format;
+            }
+            Msg::MoveToSideboard(name) => {
+                if let Some(deck) = &mut self.deck {
+                    deck.move_to_sideboard(&name);
+                }
+                self.save();
+            }
+            Msg::MoveToMain(name) => {
+                if let Some(deck) = &mut self.deck {
+                    deck.move_to_main(&name);
+                }
+                self.save();
+            }
+            Msg::Undo() => {
+                match self.undo_stack.pop() {
+                    Some(snapshot) => {
+                        self.draft.resume(snapshot);
+                        // undoing the last pick goes back to drafting, dropping any deck building
+                        if !self.draft.is_finished() {
+                            self.deck = None;
                         }
+                        self.selected.clear();
+                        self.clamp_focus();
+                        self.reset_timer();
+                        self.save();
                     }
-                    self.selected.push(display);
+                    None => return false
                 }
             }
-            Msg::Confirm() => {
-                for card in self.selected.clone() {
-                    self.pick_card(card.name);
+            Msg::ChangeUndoLimit(limit) => {
+                self.setup_info.undo_limit = limit;
+            }
+            Msg::Tick() => {
+                if self.seconds_left > 1 {
+                    self.seconds_left -= 1;
+                } else {
+                    self.auto_pick();
                 }
-                self.selected.clear();
             }
-            Msg::Export() => {
-                let mut export_map = HashMap::<String, i16>::new();
-                for pick_column in &self.sorted_picks {
-                    for card in pick_column {
-                        if !export_map.contains_key(&card.name) {
-                            export_map.insert(card.name.clone(), 0);
+            Msg::ChangePickTimer(seconds) => {
+                self.setup_info.pick_timer = seconds;
+            }
+            Msg::KeyDown(event) => {
+                if !self.is_picking() || event.ctrl_key() || event.alt_key() || event.meta_key() || browser::is_typing(&event) {
+                    return false;
+                }
+                let action = match keyboard::key_action(&event.key()) {
+                    Some(action) => action,
+                    None => return false
+                };
+                // keep Space from scrolling the page, and Space or Enter from also clicking a focused button
+                event.prevent_default();
+                let names:Vec<String> = self.pack_cards().iter().map(|card| card.name.clone()).collect();
+                match action {
+                    KeyAction::Move(direction) => {
+                        self.focus = keyboard::move_focus(self.focus, names.len(), browser::grid_columns("pack-grid"), direction);
+                    }
+                    KeyAction::Toggle => {
+                        match self.focus.and_then(|focus| names.get(focus)) {
+                            Some(name) => self.link.send_message(Msg::Select(name.clone())),
+                            None => return false
                         }
-                        export_map.insert(card.name.clone(),export_map[&card.name] + 1);
+                    }
+                    KeyAction::ToggleIndex(idx) => {
+                        match names.get(idx) {
+                            Some(name) => {
+                                self.focus = Some(idx);
+                                self.link.send_message(Msg::Select(name.clone()));
+                            }
+                            None => return false
+                        }
+                    }
+                    KeyAction::Confirm => {
+                        if self.selected.len() < self.draft.picks_required() {
+                            return false;
+                        }
+                        self.link.send_message(Msg::Confirm());
+                    }
+                    KeyAction::ToggleHelp => self.show_shortcuts = !self.show_shortcuts,
+                    KeyAction::CloseHelp => {
+                        if !self.show_shortcuts {
+                            return false;
+                        }
+                        self.show_shortcuts = false;
                     }
                 }
-                let mut export_list = vec![];
-                for card_name in export_map.keys() {
-                    export_list.push(format!("{} {}", export_map[card_name], card_name))
+            }
+            Msg::ToggleShortcuts() => {
+                self.show_shortcuts = !self.show_shortcuts;
+            }
+            Msg::Review(idx) => {
+                self.review = idx;
+                self.export_message = None;
+            }
+            Msg::ExportHistory() => {
+                let date:String = String::from(js_sys::Date::new_0().to_iso_string()).chars().take(10).collect();
+                let file_name = format!("{}-history-{}.json", self.drafted_sets().join("-"), date);
+                let json = history::history_to_json(&self.setup_info, self.draft.history());
+                self.export_message = Some(match browser::download_text(&file_name, &json) {
+                    Ok(()) => Ok(format!("Saved {}", file_name)),
+                    Err(e) => Err(format!("Could not save {}: {}", file_name, e.as_string().unwrap_or_default()))
+                });
+            }
+            Msg::ChangeGrouping(grouping) => {
+                self.grouping = grouping;
+            }
+            Msg::ApplySuggestedLands() => {
+                if let Some(deck) = &mut self.deck {
+                    let stats = PoolStats::from_cards(&deck.main);
+                    let suggested = stats.suggested_lands(stats::DEFAULT_LANDS.saturating_sub(stats.lands));
+                    for (land, color) in deck::BASIC_LANDS.iter() {
+                        let count = suggested.iter().find(|(suggested_color, _)| suggested_color == color).map(|(_, lands)| *lands).unwrap_or(0);
+                        deck.set_basic_land_count(land, count as u16);
+                    }
+                }
+                self.save();
+            }
+            Msg::ChangeBasicLand(name, count) => {
+                if let Some(deck) = &mut self.deck {
+                    deck.set_basic_land_count(&name, count);
                 }
-                web_sys::window().unwrap().navigator().clipboard().write_text(&export_list.join("\n"));
+                self.save();
             }
             Msg::StartDraft() => {
-                self.in_draft = true;
-                self.set_generator.init_from_setup_packs(&self.setup_info.sets, self.get_random_set_num());
-                self.pack = self.set_generator.generate_pack();
+                self.start_draft();
+            }
+            Msg::ResumeDraft() => {
+                if let Some(saved) = self.saved_draft.take() {
+                    self.setup_info = saved.setup_info;
+                    self.draft.resume(saved.draft);
+                    self.deck = saved.deck;
+                    self.review = None;
+                    self.undo_stack = UndoStack::new(self.setup_info.undo_limit as usize);
+                    self.ensure_deck();
+                    self.selected.clear();
+                    self.in_draft = true;
+                    self.reset_timer();
+                }
+            }
+            Msg::DismissError() => {
+                self.error = None;
+            }
+            Msg::DiscardSavedDraft() => {
+                self.saved_draft = None;
+                storage::discard_draft();
+            }
+            Msg::ChangeSeed(seed) => {
+                match seed.trim().parse() {
+                    Ok(seed) => self.setup_info.seed = seed,
+                    Err(_) => return false
+                }
+            }
+            Msg::LoadPoolFile(file) => {
+                let callback = self.link.callback(Msg::PoolFileLoaded);
+                match self.reader.read_file(file, callback) {
+                    Ok(task) => self.reader_task = Some(task),
+                    Err(e) => self.pool_message = Some(format!("Could not read file: {}", e))
+                }
+            }
+            Msg::PoolFileLoaded(file) => {
+                self.reader_task = None;
+                let FileData{name, content} = file;
+                let templates = self.draft.generator().templates();
+                let set_names = self.draft.generator().set_names();
+                let parsed = String::from_utf8(content)
+                    .map_err(|_| format!("{} is not a text file", name))
+                    .and_then(|contents| custom_sets::parse_pool_file(&name, &contents))
+                    .and_then(|sets| {
+                        let clashes = custom_sets::clashing_sets(&sets, &set_names, &self.custom_sets);
+                        if clashes.is_empty() {
+                            Ok(sets)
+                        } else {
+                            Err(format!("{} was not added: {} is already a built-in set, rename it and upload again", name, clashes.join(", ")))
+                        }
+                    })
+                    .and_then(|sets| {
+                        // refuse sets that would fail once a draft reached them
+                        let report = validate::validate_sets(&sets, templates);
+                        let first_error = report.errors().next().map(|error| error.to_string());
+                        match first_error {
+                            Some(error) => Err(format!("{} was not added: {} ({} problems in all)", name, error, report.errors().count())),
+                            None => Ok(sets)
+                        }
+                    });
+                match parsed {
+                    Ok(sets) => {
+                        let names:Vec<String> = sets.keys().cloned().collect();
+                        self.custom_sets.extend(sets.clone());
+                        storage::save_custom_sets(&self.custom_sets);
+                        self.draft.add_sets(sets);
+                        for name in &names {
+                            if !self.setup_info.sets.iter().any(|(set, _)| set == name) {
+                                self.setup_info.sets.push((name.clone(), 0));
+                            }
+                        }
+                        self.pool_message = Some(format!("Added {}", names.join(", ")));
+                    }
+                    Err(e) => self.pool_message = Some(e)
+                }
+            }
+            Msg::ChangeRatingsSet(set) => {
+                self.ratings_set = Some(set);
+            }
+            Msg::LoadRatingsFile(file) => {
+                let callback = self.link.callback(Msg::RatingsFileLoaded);
+                match self.reader.read_file(file, callback) {
+                    Ok(task) => self.reader_task = Some(task),
+                    Err(e) => self.ratings_message = Some(format!("Could not read file: {}", e))
+                }
+            }
+            Msg::RatingsFileLoaded(file) => {
+                self.reader_task = None;
+                let FileData{name, content} = file;
+                let parsed = String::from_utf8(content)
+                    .map_err(|_| format!("{} is not a text file", name))
+                    .and_then(|contents| ratings::parse_ratings_file(&name, &contents));
+                match parsed {
+                    Ok(ratings) => {
+                        let set = self.ratings_set();
+                        self.ratings_message = Some(format!("Loaded {} ratings for {}", ratings.len(), set));
+                        self.set_ratings.insert(set, ratings);
+                        storage::save_ratings(&self.set_ratings);
+                        self.apply_ratings();
+                    }
+                    Err(e) => self.ratings_message = Some(e)
+                }
+            }
+            Msg::ToggleSortByRating() => {
+                self.sort_by_rating = !self.sort_by_rating;
+            }
+            Msg::ChangeFormat(format) => {
+                self.setup_info.format = format;
+                // take packs back from the last sets if the table no longer fits
+                let mut excess = -self.get_random_set_num();
+                for set in self.setup_info.sets.iter_mut().rev() {
+                    let removed = excess.min(set.1).max(0);
+                    set.1 -= removed;
+                    excess -= removed;
+                }
+            }
+            Msg::ChangeBotStrategy(seat, strategy) => {
+                let strategies = &mut self.setup_info.bot_strategies;
+                if strategies.len() <= seat {
+                    strategies.resize(seat + 1, StrategyKind::default());
+                }
+                strategies[seat] = strategy;
             }
             Msg::AddSet() => {
                 self.setup_info.sets.push((self.get_unused_sets(String::new()).first().unwrap().clone(), 0));
@@ -593,8 +1372,21 @@ impl Component for Model {
         html!{
             <>
             <div id="main">
+            {match &self.error {
+                Some(error) => html!{
+                    <div class="alert alert-danger d-flex justify-content-between align-items-center m-2" role="alert">
+                        <span>{error.to_string()}</span>
+                        <button type="button" class="close" aria-label="Dismiss" onclick=self.link.callback(|_| Msg::DismissError())>{"×"}</button>
+                    </div>
+                },
+                None => html!{}
+            }}
             {if self.in_draft {
-                self.draft_screen()
+                match (self.review, &self.deck) {
+                    (Some(idx), _) => self.review_screen(idx),
+                    (None, Some(deck)) => self.deck_screen(deck),
+                    (None, None) => self.draft_screen()
+                }
             } else {
                 self.setup_screen()
             }}
@@ -0,0 +1,103 @@
+//! Pod drafting: bot seats sitting around the human, with packs passed between them.
+//!
+//! The human is seat 0 and the bots follow in order, so passing left hands
+//! each pack to the next seat and passing right hands it to the previous one.
+
+use serde::{Serialize, Deserialize};
+
+use crate::draft::{Card, Pack, SetGenerator};
+use crate::rng::DraftRng;
+use crate::strategy::{PickContext, StrategyKind};
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum PassDirection {
+    Left,
+    Right
+}
+
+impl PassDirection {
+    /// Packs go left in the first round and alternate after that.
+    pub fn for_round(round:i16) -> Self {
+        if round % 2 == 0 {
+            PassDirection::Left
+        } else {
+            PassDirection::Right
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Seat {
+    pub name:String,
+    pub pack:Pack,
+    pub picks:Vec<Card>,
+    #[serde(default)]
+    pub strategy:StrategyKind
+}
+
+impl Seat {
+    /// Lets the seat's strategy choose from its pack and takes the chosen cards.
+    pub fn pick_with_strategy(&mut self, context:&PickContext, rng:&mut dyn DraftRng) {
+        let names = self.strategy.strategy().choose(&self.pack, &self.picks, context, rng);
+        for name in names.iter().take(context.num_picks) {
+            if let Some(idx) = self.pack.cards.iter().position(|card| &card.name == name) {
+                let card = self.pack.cards.remove(idx);
+                self.picks.push(card);
+                self.pack.num_picks += 1;
+            }
+        }
+    }
+}
+
+/// The bot seats of a draft; empty when drafting alone.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Pod {
+    pub bots:Vec<Seat>
+}
+
+impl Pod {
+    pub fn new (num_bots:usize) -> Self {
+        Pod{bots:(1..=num_bots).map(|seat| Seat{name:format!("Bot {}", seat), ..Seat::default()}).collect()}
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bots.is_empty()
+    }
+
+    pub fn open_packs(&mut self, generator:&SetGenerator, pack_size:Option<u16>, rng:&mut dyn DraftRng) {
+        for bot in &mut self.bots {
+            bot.pack = generator.generate_pack(pack_size, rng);
+        }
+    }
+
+    /// Sets each bot's strategy in seat order; bots past the end of `strategies` keep theirs.
+    pub fn set_strategies(&mut self, strategies:&[StrategyKind]) {
+        for (bot, strategy) in self.bots.iter_mut().zip(strategies) {
+            bot.strategy = *strategy;
+        }
+    }
+
+    pub fn bots_pick(&mut self, context:&PickContext, rng:&mut dyn DraftRng) {
+        for bot in &mut self.bots {
+            bot.pick_with_strategy(context, rng);
+        }
+    }
+
+    /// Moves every pack, including the human's, one seat along.
+    pub fn pass(&mut self, human_pack:&mut Pack, direction:PassDirection) {
+        let mut packs = vec![std::mem::take(human_pack)];
+        packs.extend(self.bots.iter_mut().map(|bot| std::mem::take(&mut bot.pack)));
+        match direction {
+            PassDirection::Left => packs.rotate_right(1),
+            PassDirection::Right => packs.rotate_left(1)
+        }
+        let mut packs = packs.into_iter().map(|mut pack| {
+            pack.num_picks = 0;
+            pack
+        });
+        *human_pack = packs.next().unwrap();
+        for (bot, pack) in self.bots.iter_mut().zip(packs) {
+            bot.pack = pack;
+        }
+    }
+}
@@ -0,0 +1,164 @@
+//! Card ratings: a score per card name, higher being a better pick.
+//!
+//! Tables are loaded per set from files saved locally, e.g. 17lands-style
+//! exports, and a card is looked up in the table of the set it was opened from:
+//!
+//! * JSON mapping card names to scores, as numbers or strings like `"58.3%"`
+//! * CSV with a header row, a `Name` column and a score column, taken from the
+//!   first of `Score`, `Rating`, `GIH WR` or `GP WR`, otherwise the first
+//!   column holding numbers
+//!
+//! Cards with a blank or unreadable score are left out, so they show as unrated.
+
+use std::collections::BTreeMap;
+
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+
+use crate::draft::Card;
+
+const SCORE_COLUMNS:[&str; 4] = ["score", "rating", "gih wr", "gp wr"];
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Ratings {
+    scores:BTreeMap<String, f64>
+}
+
+impl Ratings {
+    pub fn new (scores:BTreeMap<String, f64>) -> Self {
+        Ratings{scores}
+    }
+
+    /// The card's score, or `None` for cards missing from the table.
+    pub fn get(&self, card_name:&str) -> Option<f64> {
+        self.scores.get(card_name).cloned()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.scores.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.scores.len()
+    }
+
+}
+
+/// A ratings table per set code, so a card name shared by two sets keeps a score for each.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SetRatings {
+    tables:BTreeMap<String, Ratings>
+}
+
+impl SetRatings {
+    pub fn new (tables:BTreeMap<String, Ratings>) -> Self {
+        SetRatings{tables}
+    }
+
+    /// The card's score in its own set's table, or `None` if that set has no table or the table lacks the card.
+    pub fn get(&self, card:&Card) -> Option<f64> {
+        self.tables.get(&card.set)?.get(&card.name)
+    }
+
+    /// Replaces the table for `set`.
+    pub fn insert(&mut self, set:String, ratings:Ratings) {
+        self.tables.insert(set, ratings);
+    }
+
+    pub fn tables(&self) -> &BTreeMap<String, Ratings> {
+        &self.tables
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tables.values().all(Ratings::is_empty)
+    }
+
+    /// Orders cards best rated first, with unrated cards after them in their original order.
+    pub fn sort_cards(&self, cards:&mut [&Card]) {
+        cards.sort_by(|a, b| {
+            match (self.get(a), self.get(b)) {
+                (Some(a), Some(b)) => b.partial_cmp(&a).unwrap_or(std::cmp::Ordering::Equal),
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => std::cmp::Ordering::Equal
+            }
+        });
+    }
+}
+
+/// Parses an uploaded ratings file, JSON if it looks like it and CSV otherwise.
+pub fn parse_ratings_file(file_name:&str, contents:&str) -> Result<Ratings, String> {
+    let ratings = if contents.trim_start().starts_with('{') {
+        let value:Value = serde_json::from_str(contents).map_err(|e| format!("{} is not valid JSON: {}", file_name, e))?;
+        let object = value.as_object().ok_or_else(|| format!("{} does not contain a JSON object", file_name))?;
+        let scores = object.iter()
+            .filter_map(|(name, score)| {
+                let score = match score {
+                    Value::Number(number) => number.as_f64(),
+                    Value::String(text) => parse_score(text),
+                    _ => None
+                };
+                Some((name.clone(), score?))
+            })
+            .collect();
+        Ratings::new(scores)
+    } else {
+        parse_ratings_csv(contents).map_err(|e| format!("{}: {}", file_name, e))?
+    };
+    if ratings.is_empty() {
+        return Err(format!("{} has no ratings", file_name));
+    }
+    Ok(ratings)
+}
+
+pub fn parse_ratings_csv(contents:&str) -> Result<Ratings, String> {
+    let mut lines = contents.lines().filter(|line| !line.trim().is_empty());
+    let header:Vec<String> = split_csv_line(lines.next().ok_or("the file is empty")?)
+        .iter().map(|column| column.to_lowercase()).collect();
+    let name_column = header.iter().position(|column| column == "name" || column == "card" || column == "card name")
+        .ok_or("no Name column")?;
+    let rows:Vec<Vec<String>> = lines.map(split_csv_line).collect();
+    let score_column = SCORE_COLUMNS.iter()
+        .find_map(|wanted| header.iter().position(|column| column == wanted))
+        .or_else(|| (0..header.len()).find(|idx| *idx != name_column
+            && rows.iter().any(|row| row.get(*idx).and_then(|score| parse_score(score)).is_some())))
+        .ok_or("no score column")?;
+    let scores = rows.iter()
+        .filter_map(|row| {
+            let name = row.get(name_column)?.trim();
+            let score = parse_score(row.get(score_column)?)?;
+            if name.is_empty() {
+                None
+            } else {
+                Some((String::from(name), score))
+            }
+        })
+        .collect();
+    Ok(Ratings::new(scores))
+}
+
+fn parse_score(text:&str) -> Option<f64> {
+    text.trim().trim_end_matches('%').trim().parse().ok().filter(|score:&f64| score.is_finite())
+}
+
+// splits on commas outside double quotes, with `""` standing for a quote inside them
+fn split_csv_line(line:&str) -> Vec<String> {
+    let mut fields = vec![];
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c)
+        }
+    }
+    fields.push(field);
+    fields
+}
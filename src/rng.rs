@@ -0,0 +1,51 @@
+//! Random number generation for the draft engine.
+//!
+//! Everything random in a draft (pack series order, random set choice, card
+//! pulls) goes through a `DraftRng`, so a draft started from the same seed and
+//! setup always produces the same packs.
+
+use serde::{Serialize, Deserialize};
+
+pub trait DraftRng {
+    /// Next 64 uniformly distributed bits.
+    fn next_u64(&mut self) -> u64;
+
+    /// Uniformly distributed number in `[0, 1)`.
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Uniformly distributed index in `0..len`.
+    fn below(&mut self, len:usize) -> usize {
+        (self.next_f64() * len as f64) as usize
+    }
+}
+
+/// SplitMix64: small, fast and fully determined by its seed.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SeededRng {
+    state:u64
+}
+
+impl SeededRng {
+    pub fn new (seed:u64) -> Self {
+        SeededRng{state:seed}
+    }
+}
+
+impl DraftRng for SeededRng {
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+pub fn shuffle<T, R:DraftRng + ?Sized>(vec:&mut [T], rng:&mut R) {
+    for i in 0..vec.len() {
+        let j = rng.below(vec.len());
+        vec.swap(i, j);
+    }
+}
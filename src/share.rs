@@ -0,0 +1,103 @@
+//! Encoding a draft setup into a URL fragment so the same draft can be shared.
+//!
+//! The fragment looks like `sets=MH1:10,WAR:8&seed=42&packs=18&picks=2&start=1`;
+//! `start` asks the page to skip the setup screen and open the first pack right
+//! away. `packs`, `picks`, `size` and `seats` describe the format and default to the
+//! supreme format when missing, as in links made before formats were configurable.
+//! `bots=random,color` lists the bots' pick strategies in seat order, and
+//! `undo=0` turns undo off (any other number sets how many picks can be undone).
+//! `timer=60` gives every pack a 60 second pick timer. Set names are
+//! percent-encoded, so custom sets named with `:`, `,` or `&` survive the link.
+
+use crate::draft::{percent_encode, DraftFormat, SetupInfo};
+use crate::strategy::StrategyKind;
+use crate::undo::DEFAULT_UNDO_LIMIT;
+
+pub fn setup_to_query(setup:&SetupInfo, start:bool) -> String {
+    let sets:Vec<String> = setup.sets.iter().map(|(set, num_packs)| format!("{}:{}", percent_encode(set), num_packs)).collect();
+    let format = &setup.format;
+    let mut query = format!("sets={}&seed={}&packs={}&picks={}", sets.join(","), setup.seed, format.num_packs, format.picks_per_pack);
+    if let Some(pack_size) = format.pack_size {
+        query.push_str(&format!("&size={}", pack_size));
+    }
+    if format.is_pod() {
+        query.push_str(&format!("&seats={}", format.seats));
+    }
+    if !setup.bot_strategies.is_empty() {
+        let bots:Vec<&str> = setup.bot_strategies.iter().map(|strategy| strategy.code()).collect();
+        query.push_str(&format!("&bots={}", bots.join(",")));
+    }
+    if setup.undo_limit != DEFAULT_UNDO_LIMIT {
+        query.push_str(&format!("&undo={}", setup.undo_limit));
+    }
+    if let Some(seconds) = setup.pick_timer {
+        query.push_str(&format!("&timer={}", seconds));
+    }
+    if start {
+        query.push_str("&start=1");
+    }
+    query
+}
+
+/// Reads back a query made by `setup_to_query`, with or without a leading `#` or `?`.
+///
+/// Returns `None` unless both the sets and the seed are present and well formed.
+pub fn setup_from_query(query:&str) -> Option<(SetupInfo, bool)> {
+    let query = query.trim_start_matches(['#', '?']);
+    let mut sets = None;
+    let mut seed = None;
+    let mut start = false;
+    let mut format = DraftFormat::default();
+    let mut bot_strategies = vec![];
+    let mut undo_limit = DEFAULT_UNDO_LIMIT;
+    let mut pick_timer = None;
+    for pair in query.split('&') {
+        let mut split = pair.splitn(2, '=');
+        let key = split.next()?;
+        let value = split.next().unwrap_or("");
+        match key {
+            "sets" => {
+                let mut parsed = vec![];
+                for entry in value.split(',').filter(|entry| !entry.is_empty()) {
+                    let mut split = entry.splitn(2, ':');
+                    let set = percent_decode(split.next()?)?;
+                    let num_packs:i16 = split.next()?.parse().ok()?;
+                    if set.is_empty() || num_packs < 0 {
+                        return None;
+                    }
+                    parsed.push((set, num_packs));
+                }
+                sets = Some(parsed);
+            }
+            "seed" => seed = Some(value.parse().ok()?),
+            "packs" => format.num_packs = value.parse().ok().filter(|num_packs| *num_packs > 0)?,
+            "picks" => format.picks_per_pack = value.parse().ok().filter(|picks| *picks > 0)?,
+            "size" => format.pack_size = Some(value.parse().ok().filter(|pack_size| *pack_size > 0)?),
+            "seats" => format.seats = value.parse().ok().filter(|seats| *seats > 0)?,
+            "bots" => bot_strategies = value.split(',').map(StrategyKind::from_code).collect::<Option<_>>()?,
+            "undo" => undo_limit = value.parse().ok()?,
+            "timer" => pick_timer = Some(value.parse().ok().filter(|seconds| *seconds > 0)?),
+            "start" => start = value == "1",
+            _ => {}
+        }
+    }
+    let sets = sets.filter(|sets| !sets.is_empty())?;
+    Some((SetupInfo{sets, seed:seed?, format, bot_strategies, undo_limit, pick_timer}, start))
+}
+
+/// Undoes `percent_encode`; `None` for a malformed escape or text that is not UTF-8.
+fn percent_decode(text:&str) -> Option<String> {
+    let mut bytes = vec![];
+    let mut rest = text.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'%' {
+            let hex = std::str::from_utf8(tail.get(..2)?).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
@@ -0,0 +1,143 @@
+//! Opening many packs headlessly to check collation against what a set's template intends.
+//!
+//! Each sample lays out the setup's pack series and opens every pack in it
+//! the way a draft does, switching sets as the series goes, so problems that
+//! only show after a set change (cards left over from the previous set's
+//! pools) turn up as well as thin pools and skewed rarities.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::draft::{Pack, SetGenerator};
+use crate::error::DraftError;
+use crate::rng::DraftRng;
+use crate::template::PackTemplate;
+
+/// What was seen opening `packs` packs of one set.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct PackStats {
+    pub set:String,
+    pub packs:usize,
+    /// Cards in each rarity pool, `b` being the basic lands.
+    pub pool_sizes:BTreeMap<char, usize>,
+    /// Mythics per pack, against the rate the template's weights give (1/8 for most sets).
+    pub mythic_rate:f64,
+    pub intended_mythic_rate:f64,
+    /// Share of packs holding the same card twice.
+    pub duplicate_rate:f64,
+    /// Share of packs with a basic land.
+    pub basic_fill:f64,
+    /// Share of packs with a slot that ran out of cards, see `Pack::short_slots`.
+    pub short_rate:f64,
+    pub average_size:f64,
+    /// Cards from another set, which a draft of this set should never show.
+    pub foreign_cards:usize,
+    /// How often each card was in a pack, most frequent first.
+    pub card_rates:Vec<(String, f64)>
+}
+
+#[derive(Default)]
+struct Counts {
+    packs:usize,
+    cards:usize,
+    mythics:usize,
+    duplicates:usize,
+    basics:usize,
+    short:usize,
+    foreign_cards:usize,
+    pool_sizes:BTreeMap<char, usize>,
+    intended_mythic_rate:f64,
+    appearances:BTreeMap<String, usize>
+}
+
+/// Mythics per pack if every rarity in the template's slots had cards.
+pub fn intended_mythic_rate(template:&PackTemplate) -> f64 {
+    template.slots.iter().map(|slot| {
+        let total:u32 = slot.rarities.values().sum();
+        let mythic = slot.rarities.get(&'m').cloned().unwrap_or(0);
+        if total == 0 { 0. } else { slot.count as f64 * mythic as f64 / total as f64 }
+    }).sum()
+}
+
+/// Opens every pack of `setup`'s series `samples` times, optionally resized to `pack_size`, and reports per set.
+pub fn simulate_packs(generator:&mut SetGenerator, setup:&[(String, i16)], samples:usize, pack_size:Option<u16>, rng:&mut dyn DraftRng) -> Result<Vec<PackStats>, DraftError> {
+    let mut counts:BTreeMap<String, Counts> = BTreeMap::new();
+    for _ in 0..samples {
+        generator.init_from_setup_packs(setup, 0, rng)?;
+        while (generator.pack_number as usize) < generator.pack_series.len() {
+            let set = generator.pack_series[generator.pack_number as usize].clone();
+            let pack = generator.generate_pack(pack_size, rng);
+            let counts = counts.entry(set.clone()).or_default();
+            if counts.packs == 0 {
+                counts.pool_sizes = generator.pool_sizes();
+                counts.intended_mythic_rate = intended_mythic_rate(generator.current_template());
+            }
+            record_pack(counts, &set, &pack, generator);
+            generator.progress_pack()?;
+        }
+    }
+    Ok(counts.into_iter().map(|(set, counts)| {
+        let packs = counts.packs.max(1) as f64;
+        let mut card_rates:Vec<(String, f64)> = counts.appearances.into_iter().map(|(name, seen)| (name, seen as f64 / packs)).collect();
+        card_rates.sort_by(|a, b| b.1.total_cmp(&a.1));
+        PackStats{
+            set,
+            packs:counts.packs,
+            pool_sizes:counts.pool_sizes,
+            mythic_rate:counts.mythics as f64 / packs,
+            intended_mythic_rate:counts.intended_mythic_rate,
+            duplicate_rate:counts.duplicates as f64 / packs,
+            basic_fill:counts.basics as f64 / packs,
+            short_rate:counts.short as f64 / packs,
+            average_size:counts.cards as f64 / packs,
+            foreign_cards:counts.foreign_cards,
+            card_rates
+        }
+    }).collect())
+}
+
+fn record_pack(counts:&mut Counts, set:&str, pack:&Pack, generator:&SetGenerator) {
+    let cards = &pack.cards;
+    counts.packs += 1;
+    if pack.is_short() {
+        counts.short += 1;
+    }
+    counts.cards += cards.len();
+    counts.mythics += cards.iter().filter(|card| card.rarity == 'm').count();
+    counts.foreign_cards += cards.iter().filter(|card| card.set != set).count();
+    if cards.iter().any(|card| generator.is_basic(&card.name)) {
+        counts.basics += 1;
+    }
+    if cards.iter().enumerate().any(|(idx, card)| cards[..idx].contains(card)) {
+        counts.duplicates += 1;
+    }
+    for card in cards {
+        *counts.appearances.entry(card.name.clone()).or_default() += 1;
+    }
+}
+
+/// A readable summary, listing only the `extremes` most and least frequent cards.
+pub fn stats_to_text(stats:&[PackStats], extremes:usize) -> String {
+    stats.iter().map(|stats| {
+        let pools:Vec<String> = stats.pool_sizes.iter().map(|(rarity, size)| format!("{} {}", rarity, size)).collect();
+        let mut text = format!("{} ({} packs)\n", stats.set, stats.packs);
+        text.push_str(&format!("  pools: {}\n", pools.join(", ")));
+        text.push_str(&format!("  mythics per pack: {:.4} (intended {:.4})\n", stats.mythic_rate, stats.intended_mythic_rate));
+        text.push_str(&format!("  packs with a duplicate: {:.2}%\n", stats.duplicate_rate * 100.));
+        text.push_str(&format!("  packs with a basic land: {:.2}%\n", stats.basic_fill * 100.));
+        text.push_str(&format!("  average pack size: {:.2}\n", stats.average_size));
+        if stats.short_rate > 0. {
+            text.push_str(&format!("  packs that ran out of cards: {:.2}%\n", stats.short_rate * 100.));
+        }
+        if stats.foreign_cards > 0 {
+            text.push_str(&format!("  cards from other sets: {}\n", stats.foreign_cards));
+        }
+        let rate_line = |(name, rate):&(String, f64)| format!("    {:.4} {}\n", rate, name);
+        text.push_str("  most frequent:\n");
+        text.extend(stats.card_rates.iter().take(extremes).map(rate_line));
+        text.push_str("  least frequent:\n");
+        text.extend(stats.card_rates.iter().rev().take(extremes).map(rate_line));
+        text
+    }).collect::<Vec<_>>().join("\n")
+}
@@ -0,0 +1,88 @@
+//! Summaries of a pool or deck: mana curve, color pips, card types and a suggested land split.
+
+use crate::draft::{is_basic_land, Card};
+
+/// Lands suggested for a 40 card deck.
+pub const DEFAULT_LANDS:usize = 17;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PoolStats {
+    /// Nonland cards at each CMC, the last bucket holding the highest CMC seen.
+    pub curve:Vec<usize>,
+    /// Colored mana symbols in WUBRG order.
+    pub pips:[usize; 5],
+    pub creatures:usize,
+    /// Nonland cards that aren't creatures.
+    pub non_creatures:usize,
+    pub lands:usize,
+    /// Cards without a type line, such as those of the bundled sets, other than basic lands.
+    pub unknown:usize
+}
+
+impl PoolStats {
+    pub fn from_cards<'a>(cards:impl IntoIterator<Item = &'a Card>) -> Self {
+        let mut stats = PoolStats::default();
+        for card in cards {
+            if card.is_land() || (card.type_line.is_none() && is_basic_land(&card.name)) {
+                stats.lands += 1;
+                continue;
+            }
+            if card.type_line.is_none() {
+                stats.unknown += 1;
+            } else if card.is_creature() {
+                stats.creatures += 1;
+            } else {
+                stats.non_creatures += 1;
+            }
+            let cmc = card.cmc.max(0) as usize;
+            if stats.curve.len() <= cmc {
+                stats.curve.resize(cmc + 1, 0);
+            }
+            stats.curve[cmc] += 1;
+            for (pips, count) in stats.pips.iter_mut().zip(card_pips(card).iter()) {
+                *pips += count;
+            }
+        }
+        stats
+    }
+
+    /// Basic lands per color in proportion to the pips, adding up to `num_lands`.
+    ///
+    /// Colors without pips get none; with no pips at all the split is empty.
+    pub fn suggested_lands(&self, num_lands:usize) -> Vec<(char, usize)> {
+        let total_pips:usize = self.pips.iter().sum();
+        if total_pips == 0 {
+            return vec![];
+        }
+        let mut split:Vec<(char, usize, usize)> = "WUBRG".chars().zip(self.pips.iter())
+            .filter(|(_, pips)| **pips > 0)
+            .map(|(color, pips)| (color, pips * num_lands / total_pips, pips * num_lands % total_pips))
+            .collect();
+        // hand out what rounding down left over to the largest remainders
+        let mut left = num_lands - split.iter().map(|(_, lands, _)| lands).sum::<usize>();
+        let mut order:Vec<usize> = (0..split.len()).collect();
+        order.sort_by_key(|idx| std::cmp::Reverse(split[*idx].2));
+        for idx in order {
+            if left == 0 {
+                break;
+            }
+            split[idx].1 += 1;
+            left -= 1;
+        }
+        split.into_iter().map(|(color, lands, _)| (color, lands)).filter(|(_, lands)| *lands > 0).collect()
+    }
+}
+
+/// Colored mana symbols in the card's cost in WUBRG order, hybrid symbols counting for each color.
+///
+/// Cards without a mana cost count one pip per color they are.
+pub fn card_pips(card:&Card) -> [usize; 5] {
+    let mut pips = [0; 5];
+    for (idx, color) in "WUBRG".chars().enumerate() {
+        pips[idx] = match &card.mana_cost {
+            Some(mana_cost) => mana_cost.matches(color).count(),
+            None => card.colors.contains(color) as usize
+        };
+    }
+    pips
+}
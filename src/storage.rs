@@ -0,0 +1,83 @@
+//! Saving the draft in progress to `window.localStorage` so a reload can resume it.
+
+use serde::{Serialize, Deserialize};
+use serde_json::{Value, Map};
+
+use crate::deck::Deck;
+use crate::draft::{Draft, SetupInfo};
+use crate::ratings::SetRatings;
+
+const SAVED_DRAFT_KEY:&str = "supreme-drafter.saved-draft";
+
+#[derive(Deserialize)]
+pub struct SavedDraft {
+    pub setup_info:SetupInfo,
+    pub draft:Draft,
+    /// Deck building progress, once the draft is finished.
+    #[serde(default)]
+    pub deck:Option<Deck>
+}
+
+// Borrowing twin of `SavedDraft`, so saving doesn't have to clone the draft.
+#[derive(Serialize)]
+struct SavedDraftRef<'a> {
+    setup_info:&'a SetupInfo,
+    draft:&'a Draft,
+    deck:Option<&'a Deck>
+}
+
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+pub fn save_draft(setup_info:&SetupInfo, draft:&Draft, deck:Option<&Deck>) {
+    let saved = SavedDraftRef{setup_info, draft, deck};
+    if let (Some(storage), Ok(json)) = (local_storage(), serde_json::to_string(&saved)) {
+        // storage can be full or disabled; losing the save is better than interrupting the draft
+        let _ = storage.set_item(SAVED_DRAFT_KEY, &json);
+    }
+}
+
+/// The saved draft, if there is one and it can still be read by this version.
+pub fn load_draft() -> Option<SavedDraft> {
+    let json = local_storage()?.get_item(SAVED_DRAFT_KEY).ok()??;
+    serde_json::from_str(&json).ok()
+}
+
+pub fn discard_draft() {
+    if let Some(storage) = local_storage() {
+        let _ = storage.remove_item(SAVED_DRAFT_KEY);
+    }
+}
+
+const CUSTOM_SETS_KEY:&str = "supreme-drafter.custom-sets";
+
+/// Keeps uploaded pools around so they survive reloads, along with any draft using them.
+pub fn save_custom_sets(sets:&Map<String, Value>) {
+    if let (Some(storage), Ok(json)) = (local_storage(), serde_json::to_string(sets)) {
+        let _ = storage.set_item(CUSTOM_SETS_KEY, &json);
+    }
+}
+
+pub fn load_custom_sets() -> Map<String, Value> {
+    local_storage()
+        .and_then(|storage| storage.get_item(CUSTOM_SETS_KEY).ok()?)
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+const RATINGS_KEY:&str = "supreme-drafter.ratings";
+
+/// Keeps imported ratings tables, keyed by set, across reloads.
+pub fn save_ratings(ratings:&SetRatings) {
+    if let (Some(storage), Ok(json)) = (local_storage(), serde_json::to_string(ratings)) {
+        let _ = storage.set_item(RATINGS_KEY, &json);
+    }
+}
+
+pub fn load_ratings() -> SetRatings {
+    local_storage()
+        .and_then(|storage| storage.get_item(RATINGS_KEY).ok()?)
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
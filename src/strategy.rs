@@ -0,0 +1,170 @@
+//! How bots choose their picks.
+//!
+//! A `PickStrategy` only sees a pack, the seat's own picks and some context
+//! about the draft, so strategies can be swapped per seat and compared by
+//! running whole drafts headlessly.
+
+use std::cmp::{Ordering, Reverse};
+
+use serde::{Serialize, Deserialize};
+
+use crate::draft::{Card, Pack};
+use crate::ratings::SetRatings;
+use crate::rng::{DraftRng, shuffle};
+
+pub struct PickContext<'a> {
+    /// How many cards to choose from the pack.
+    pub num_picks:usize,
+    pub pack_number:i16,
+    pub num_packs:i16,
+    pub ratings:&'a SetRatings
+}
+
+pub trait PickStrategy {
+    /// Names of the cards to take from `pack`, at most `context.num_picks` of them.
+    fn choose(&self, pack:&Pack, picks:&[Card], context:&PickContext, rng:&mut dyn DraftRng) -> Vec<String>;
+}
+
+/// The built-in strategies, as stored with each seat.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StrategyKind {
+    Random,
+    RarityFirst,
+    HighestRated,
+    #[default]
+    ColorCommitted
+}
+
+impl StrategyKind {
+    pub const ALL:[StrategyKind; 4] = [StrategyKind::Random, StrategyKind::RarityFirst, StrategyKind::HighestRated, StrategyKind::ColorCommitted];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            StrategyKind::Random => "Random",
+            StrategyKind::RarityFirst => "Rarity first",
+            StrategyKind::HighestRated => "Highest rated",
+            StrategyKind::ColorCommitted => "Color committed"
+        }
+    }
+
+    /// Short name used in links and on the command line.
+    pub fn code(&self) -> &'static str {
+        match self {
+            StrategyKind::Random => "random",
+            StrategyKind::RarityFirst => "rarity",
+            StrategyKind::HighestRated => "rated",
+            StrategyKind::ColorCommitted => "color"
+        }
+    }
+
+    pub fn from_code(code:&str) -> Option<Self> {
+        StrategyKind::ALL.iter().find(|kind| kind.code() == code).cloned()
+    }
+
+    pub fn strategy(&self) -> Box<dyn PickStrategy> {
+        match self {
+            StrategyKind::Random => Box::new(RandomPick),
+            StrategyKind::RarityFirst => Box::new(RarityFirst),
+            StrategyKind::HighestRated => Box::new(HighestRated),
+            StrategyKind::ColorCommitted => Box::new(ColorCommitted::default())
+        }
+    }
+}
+
+/// Tops up cards the drafter already `selected` to `context.num_picks` with
+/// `strategy`'s choices from the rest of the pack, e.g. when a pick timer runs out.
+pub fn complete_selection(strategy:&dyn PickStrategy, pack:&Pack, picks:&[Card], selected:&[String], context:&PickContext, rng:&mut dyn DraftRng) -> Vec<String> {
+    let mut names:Vec<String> = selected.iter().take(context.num_picks).cloned().collect();
+    let rest = Pack{cards:pack.cards.iter().filter(|card| !names.contains(&card.name)).cloned().collect(), ..Pack::default()};
+    let context = PickContext{num_picks:context.num_picks - names.len(), ..*context};
+    names.extend(strategy.choose(&rest, picks, &context, rng));
+    names
+}
+
+pub fn rarity_rank(card:&Card) -> f64 {
+    match card.rarity {
+        'm' => 4.,
+        'r' => 3.,
+        'u' => 2.,
+        'c' => 1.,
+        _ => 0.
+    }
+}
+
+/// Rated cards ahead of unrated ones, then by rating, then by rarity.
+fn rated_key(card:&Card, ratings:&SetRatings) -> [f64; 3] {
+    match ratings.get(card) {
+        Some(rating) => [1., rating, rarity_rank(card)],
+        None => [0., 0., rarity_rank(card)]
+    }
+}
+
+/// The `num_picks` cards with the highest keys, ties broken at random.
+fn choose_by_key<K:PartialOrd>(pack:&Pack, num_picks:usize, rng:&mut dyn DraftRng, key:impl Fn(&Card) -> K) -> Vec<String> {
+    let mut cards:Vec<&Card> = pack.cards.iter().collect();
+    shuffle(&mut cards, rng);
+    cards.sort_by(|a, b| key(b).partial_cmp(&key(a)).unwrap_or(Ordering::Equal));
+    cards.iter().take(num_picks).map(|card| card.name.clone()).collect()
+}
+
+pub struct RandomPick;
+
+impl PickStrategy for RandomPick {
+    fn choose(&self, pack:&Pack, _picks:&[Card], context:&PickContext, rng:&mut dyn DraftRng) -> Vec<String> {
+        choose_by_key(pack, context.num_picks, rng, |_| 0)
+    }
+}
+
+pub struct RarityFirst;
+
+impl PickStrategy for RarityFirst {
+    fn choose(&self, pack:&Pack, _picks:&[Card], context:&PickContext, rng:&mut dyn DraftRng) -> Vec<String> {
+        choose_by_key(pack, context.num_picks, rng, rarity_rank)
+    }
+}
+
+/// Best rated cards from the ratings table, falling back to rarity for cards it doesn't have.
+pub struct HighestRated;
+
+impl PickStrategy for HighestRated {
+    fn choose(&self, pack:&Pack, _picks:&[Card], context:&PickContext, rng:&mut dyn DraftRng) -> Vec<String> {
+        choose_by_key(pack, context.num_picks, rng, |card| rated_key(card, context.ratings))
+    }
+}
+
+/// Drafts like `HighestRated` until it has `commit_after` picks, then prefers
+/// cards within its two most picked colors (colorless cards always fit).
+pub struct ColorCommitted {
+    pub commit_after:usize
+}
+
+impl Default for ColorCommitted {
+    fn default() -> Self {
+        ColorCommitted{commit_after:5}
+    }
+}
+
+impl ColorCommitted {
+    fn main_colors(picks:&[Card]) -> String {
+        let mut counts:Vec<(char, usize)> = "WUBRG".chars()
+            .map(|color| (color, picks.iter().filter(|card| card.colors.contains(color)).count()))
+            .filter(|(_, count)| *count > 0)
+            .collect();
+        counts.sort_by_key(|(_, count)| Reverse(*count));
+        counts.iter().take(2).map(|(color, _)| *color).collect()
+    }
+}
+
+impl PickStrategy for ColorCommitted {
+    fn choose(&self, pack:&Pack, picks:&[Card], context:&PickContext, rng:&mut dyn DraftRng) -> Vec<String> {
+        if picks.len() < self.commit_after {
+            return HighestRated.choose(pack, picks, context, rng);
+        }
+        let main_colors = ColorCommitted::main_colors(picks);
+        choose_by_key(pack, context.num_picks, rng, |card| {
+            let on_color = card.colors.chars().all(|color| main_colors.contains(color));
+            let [rated, rating, rarity] = rated_key(card, context.ratings);
+            [if on_color {1.} else {0.}, rated, rating, rarity]
+        })
+    }
+}
@@ -0,0 +1,123 @@
+//! Pack collation templates: which slots a booster has and how each is filled.
+//!
+//! Most sets use `PackTemplate::default()`, one rare or mythic, three
+//! uncommons, ten commons and a basic land. Sets collated differently get an
+//! override in `pack-templates.json`, keyed by set code, in this format:
+//!
+//! ```json
+//! {"2XM": {"slots": [
+//!     {"name": "rare", "count": 2, "rarities": {"r": 7, "m": 1}},
+//!     {"name": "common", "count": 8, "rarities": {"c": 1}, "filter": {"exclude_type": "Land"}}
+//! ]}}
+//! ```
+
+use std::collections::BTreeMap;
+
+use serde::{Serialize, Deserialize};
+
+use crate::draft::Card;
+
+/// Rarity pools from rarest to most common. Basic lands (`b`) are kept apart from these.
+pub const RARITY_ORDER:[char; 4] = ['m', 'r', 'u', 'c'];
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PackTemplate {
+    pub slots:Vec<PackSlot>
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PackSlot {
+    pub name:String,
+    pub count:u16,
+    /// Relative chance of each rarity pool filling the slot, e.g. `{"r": 7, "m": 1}` for
+    /// mythics in one of eight packs. `b` stands for the set's basic lands.
+    pub rarities:BTreeMap<char, u32>,
+    #[serde(default)]
+    pub filter:CardFilter,
+    /// Whether the slot may repeat a card already in the pack.
+    #[serde(default)]
+    pub duplicates:bool
+}
+
+/// Restricts which cards of a rarity pool may fill a slot. Empty fields allow everything.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct CardFilter {
+    /// Text the type line must contain, e.g. `Planeswalker`.
+    #[serde(default)]
+    pub type_contains:Option<String>,
+    /// Text the type line must not contain.
+    #[serde(default)]
+    pub exclude_type:Option<String>,
+    /// Only these cards, for slots the card data cannot describe otherwise.
+    #[serde(default)]
+    pub names:Option<Vec<String>>
+}
+
+impl CardFilter {
+    pub fn matches(&self, card:&Card) -> bool {
+        let type_line = card.type_line.as_deref().unwrap_or("");
+        if let Some(type_contains) = &self.type_contains {
+            if !type_line.contains(type_contains.as_str()) {
+                return false;
+            }
+        }
+        if let Some(exclude_type) = &self.exclude_type {
+            if type_line.contains(exclude_type.as_str()) {
+                return false;
+            }
+        }
+        match &self.names {
+            Some(names) => names.contains(&card.name),
+            None => true
+        }
+    }
+}
+
+impl PackSlot {
+    fn new (name:&str, count:u16, rarities:&[(char, u32)], duplicates:bool) -> Self {
+        PackSlot{name:String::from(name), count, rarities:rarities.iter().cloned().collect(), filter:CardFilter::default(), duplicates}
+    }
+}
+
+impl Default for PackTemplate {
+    fn default() -> Self {
+        PackTemplate{slots:vec![
+            PackSlot::new("rare", 1, &[('r', 7), ('m', 1)], false),
+            PackSlot::new("uncommon", 3, &[('u', 1)], false),
+            PackSlot::new("common", 10, &[('c', 1)], false),
+            PackSlot::new("basic land", 1, &[('b', 1)], true)
+        ]}
+    }
+}
+
+impl PackTemplate {
+    /// Number of cards a pack has when every slot can be filled.
+    pub fn pack_size(&self) -> u16 {
+        self.slots.iter().map(|slot| slot.count).sum()
+    }
+
+    /// A copy holding `pack_size` cards: extra cards go to the largest slot
+    /// (the commons), and cards are dropped starting from the last slot.
+    pub fn resized(&self, pack_size:u16) -> PackTemplate {
+        let mut template = self.clone();
+        let current_size = template.pack_size();
+        if pack_size > current_size {
+            if let Some(largest) = template.slots.iter_mut().max_by_key(|slot| slot.count) {
+                largest.count += pack_size - current_size;
+            }
+        } else {
+            let mut excess = current_size - pack_size;
+            for slot in template.slots.iter_mut().rev() {
+                let removed = excess.min(slot.count);
+                slot.count -= removed;
+                excess -= removed;
+            }
+        }
+        template
+    }
+}
+
+/// Parses `pack-templates.json` style overrides keyed by set code.
+pub fn parse_templates(json:&str) -> Result<BTreeMap<String, PackTemplate>, String> {
+    serde_json::from_str(json).map_err(|e| format!("invalid pack templates: {}", e))
+}
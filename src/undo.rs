@@ -0,0 +1,51 @@
+//! A bounded stack of earlier states for undoing picks.
+//!
+//! Draft snapshots include the random state, so undoing and making the same
+//! pick opens the same next pack and a shared seed stays reproducible. It also
+//! means a player can pick, look at the next pack and undo; that is accepted
+//! for casual drafts, and setups that want no scouting turn undo off.
+
+use std::collections::VecDeque;
+
+/// Picks that can be undone unless the setup says otherwise.
+pub const DEFAULT_UNDO_LIMIT:u16 = 10;
+
+/// Keeps the most recent `limit` states, dropping the oldest beyond that; a limit of 0 turns undo off.
+#[derive(Clone, Debug, Default)]
+pub struct UndoStack<T> {
+    states:VecDeque<T>,
+    limit:usize
+}
+
+impl<T> UndoStack<T> {
+    pub fn new (limit:usize) -> Self {
+        UndoStack{states:VecDeque::new(), limit}
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.limit > 0
+    }
+
+    pub fn push(&mut self, state:T) {
+        if !self.is_enabled() {
+            return;
+        }
+        if self.states.len() >= self.limit {
+            self.states.pop_front();
+        }
+        self.states.push_back(state);
+    }
+
+    /// The most recently pushed state, if any is left.
+    pub fn pop(&mut self) -> Option<T> {
+        self.states.pop_back()
+    }
+
+    pub fn len(&self) -> usize {
+        self.states.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.states.is_empty()
+    }
+}
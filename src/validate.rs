@@ -0,0 +1,209 @@
+//! Checking set JSON in the `small-sets.json` schema before it is drafted.
+//!
+//! A card without a mana value would otherwise only show up once a draft
+//! reaches its set, and a pool too small for its pack slots as packs that
+//! borrow or repeat cards. Validating when sets are loaded reports every such
+//! problem at once.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::draft::{is_basic_land, Card};
+use crate::template::{PackSlot, PackTemplate, RARITY_ORDER};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum Severity {
+    /// The set cannot be drafted as it is.
+    Error,
+    /// The set can be drafted, but not quite as the data intends.
+    Warning
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub enum IssueKind {
+    /// The set is not a map of card names to card objects.
+    NotASet,
+    NotACard,
+    MissingField(&'static str),
+    /// `c` is there but is not a whole number.
+    NonIntegerCmc(Value),
+    /// `r` is not one of `m`, `r`, `u` or `c`, so the card never appears.
+    UnknownRarity(String),
+    /// `i` is there but is not a whole number, so the card's image is looked up by name.
+    NonIntegerMultiverseId(Value),
+    /// Another card, named here, has the same multiverse id.
+    DuplicateMultiverseId{id:i64, other:String},
+    /// A slot that may not repeat cards needs more cards than its pools hold,
+    /// so packs borrow from other rarities or repeat cards to fill it.
+    PoolTooSmall{slot:String, needed:u16, available:usize},
+    /// No pool can fill the slot, so packs go without it.
+    EmptySlot{slot:String},
+    /// No slot but the basic land can be filled, so packs would have nothing to pick.
+    NoFillableSlots
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Issue {
+    pub severity:Severity,
+    pub set:String,
+    /// The card at fault, for problems with a single card.
+    pub card:Option<String>,
+    pub kind:IssueKind
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f:&mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning"
+        };
+        write!(f, "{}: {}", severity, self.set)?;
+        if let Some(card) = &self.card {
+            write!(f, ", {}", card)?;
+        }
+        match &self.kind {
+            IssueKind::NotASet => write!(f, ": not a map of cards"),
+            IssueKind::NotACard => write!(f, ": not a card object"),
+            IssueKind::MissingField(field) => write!(f, ": missing `{}`", field),
+            IssueKind::NonIntegerCmc(value) => write!(f, ": mana value `c` is {}, not a whole number", value),
+            IssueKind::UnknownRarity(rarity) => write!(f, ": unknown rarity {:?}", rarity),
+            IssueKind::NonIntegerMultiverseId(value) => write!(f, ": multiverse id `i` is {}, not a whole number", value),
+            IssueKind::DuplicateMultiverseId{id, other} => write!(f, ": multiverse id {} is also used by {}", id, other),
+            IssueKind::PoolTooSmall{slot, needed, available} => write!(f, ": the {} slot needs {} different cards but there are only {}", slot, needed, available),
+            IssueKind::EmptySlot{slot} => write!(f, ": nothing can fill the {} slot", slot),
+            IssueKind::NoFillableSlots => write!(f, ": no slot of its packs can be filled")
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct ValidationReport {
+    pub sets:usize,
+    pub cards:usize,
+    pub issues:Vec<Issue>
+}
+
+impl ValidationReport {
+    pub fn errors(&self) -> impl Iterator<Item = &Issue> {
+        self.issues.iter().filter(|issue| issue.severity == Severity::Error)
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.errors().next().is_some()
+    }
+
+    /// Sets with at least one error.
+    pub fn invalid_sets(&self) -> Vec<&str> {
+        let mut sets:Vec<&str> = self.errors().map(|issue| issue.set.as_str()).collect();
+        sets.dedup();
+        sets
+    }
+
+    /// One line per issue, then a count of errors and warnings.
+    pub fn to_text(&self) -> String {
+        let errors = self.errors().count();
+        let mut text:String = self.issues.iter().map(|issue| format!("{}\n", issue)).collect();
+        text.push_str(&format!("{} sets, {} cards: {} errors, {} warnings", self.sets, self.cards, errors, self.issues.len() - errors));
+        text
+    }
+}
+
+/// Checks every card of every set, and that each set's pack template (from
+/// `templates`, or the default) can be filled from its pools.
+pub fn validate_sets(sets:&Map<String, Value>, templates:&BTreeMap<String, PackTemplate>) -> ValidationReport {
+    let mut report = ValidationReport::default();
+    let mut multiverse_ids:BTreeMap<i64, String> = BTreeMap::new();
+    for (set, cards) in sets {
+        report.sets += 1;
+        let cards = match cards.as_object() {
+            Some(cards) => cards,
+            None => {
+                report.issues.push(Issue{severity:Severity::Error, set:set.clone(), card:None, kind:IssueKind::NotASet});
+                continue;
+            }
+        };
+        report.cards += cards.len();
+        let mut issues = vec![];
+        let mut issue = |severity, card:Option<&str>, kind| issues.push(Issue{severity, set:set.clone(), card:card.map(String::from), kind});
+        let mut pools:BTreeMap<char, Vec<Card>> = BTreeMap::new();
+        for (name, value) in cards {
+            let value = match value.as_object() {
+                Some(value) => value,
+                None => {
+                    issue(Severity::Error, Some(name), IssueKind::NotACard);
+                    continue;
+                }
+            };
+            match value.get("c") {
+                None => issue(Severity::Error, Some(name), IssueKind::MissingField("c")),
+                Some(cmc) if cmc.as_i64().is_none() => issue(Severity::Error, Some(name), IssueKind::NonIntegerCmc(cmc.clone())),
+                _ => {}
+            }
+            let rarity = match value.get("r").map(Value::as_str) {
+                None => {
+                    issue(Severity::Error, Some(name), IssueKind::MissingField("r"));
+                    None
+                }
+                Some(None) => {
+                    issue(Severity::Error, Some(name), IssueKind::UnknownRarity(value["r"].to_string()));
+                    None
+                }
+                Some(Some(rarity)) => Some(rarity)
+            };
+            if let Some(id) = value.get("i") {
+                match id.as_i64() {
+                    Some(id) => match multiverse_ids.get(&id) {
+                        Some(other) => issue(Severity::Warning, Some(name), IssueKind::DuplicateMultiverseId{id, other:other.clone()}),
+                        None => {
+                            multiverse_ids.insert(id, format!("{} ({})", name, set));
+                        }
+                    },
+                    None => issue(Severity::Warning, Some(name), IssueKind::NonIntegerMultiverseId(id.clone()))
+                }
+            }
+            let card = Card{name:name.clone(), type_line:value.get("t").and_then(Value::as_str).map(String::from), ..Card::default()};
+            match rarity {
+                _ if is_basic_land(name) => pools.entry('b').or_default().push(card),
+                Some(rarity @ ("m" | "r" | "u" | "c")) => pools.entry(rarity.chars().next().unwrap_or('c')).or_default().push(card),
+                Some(rarity) => issue(Severity::Warning, Some(name), IssueKind::UnknownRarity(String::from(rarity))),
+                None => {}
+            }
+        }
+        let template = templates.get(set).cloned().unwrap_or_default();
+        let mut fillable = false;
+        for slot in &template.slots {
+            let available = slot_size(slot, &pools);
+            let basic_slot = slot.rarities.keys().all(|rarity| *rarity == 'b');
+            fillable |= available > 0 && slot.count > 0 && !basic_slot;
+            if available == 0 {
+                // sets without basics simply have no basic land slot
+                if !basic_slot {
+                    issue(Severity::Warning, None, IssueKind::EmptySlot{slot:slot.name.clone()});
+                }
+            } else if !slot.duplicates && available < slot.count as usize {
+                issue(Severity::Warning, None, IssueKind::PoolTooSmall{slot:slot.name.clone(), needed:slot.count, available});
+            }
+        }
+        if !fillable {
+            issue(Severity::Error, None, IssueKind::NoFillableSlots);
+        }
+        report.issues.extend(issues);
+    }
+    report
+}
+
+/// Cards that could fill `slot`, falling back to lower rarities as `SetGenerator` does.
+fn slot_size(slot:&PackSlot, pools:&BTreeMap<char, Vec<Card>>) -> usize {
+    let filtered = |rarity:char| pools.get(&rarity).map(|cards| cards.iter().filter(|card| slot.filter.matches(card)).count()).unwrap_or(0);
+    let available:usize = slot.rarities.iter().filter(|(_, weight)| **weight > 0).map(|(rarity, _)| filtered(*rarity)).sum();
+    if available > 0 {
+        return available;
+    }
+    match RARITY_ORDER.iter().rposition(|rarity| slot.rarities.contains_key(rarity)) {
+        Some(lowest) => RARITY_ORDER[lowest+1..].iter().map(|rarity| filtered(*rarity)).find(|size| *size > 0).unwrap_or(0),
+        None => 0
+    }
+}
@@ -0,0 +1,79 @@
+//! Native tests for the command line.
+
+use supreme_drafter_rs::cli::{generate_packs, load_generator, parse_args, parse_pick, pool_file_name, run_draft, Command};
+use supreme_drafter_rs::draft::{Draft, DraftFormat};
+use supreme_drafter_rs::error::DraftError;
+use supreme_drafter_rs::rng::SeededRng;
+use supreme_drafter_rs::share::setup_from_query;
+use supreme_drafter_rs::strategy::StrategyKind;
+
+fn args(line:&str) -> Vec<String> {
+    line.split_whitespace().map(String::from).collect()
+}
+
+#[test]
+fn arguments_describe_a_setup() {
+    let options = parse_args(&args("draft --set MH1:10 --set WAR:8 --seed 42 --seats 8 --bots random,color"), 7).unwrap();
+    assert_eq!(options.command, Command::Draft);
+    assert_eq!(options.setup.sets, vec![(String::from("MH1"), 10), (String::from("WAR"), 8)]);
+    assert_eq!(options.setup.seed, 42);
+    assert_eq!(options.setup.format, DraftFormat{seats:8, ..DraftFormat::SUPREME});
+    assert_eq!(options.setup.bot_strategies, vec![StrategyKind::Random, StrategyKind::ColorCommitted]);
+    assert_eq!(parse_args(&args("--json"), 7).unwrap().setup.seed, 7);
+    assert!(parse_args(&args("--set MH1"), 7).is_err());
+    assert!(parse_args(&args("--packs 0"), 7).is_err());
+    assert!(parse_args(&args("--seed"), 7).is_err());
+    assert!(parse_args(&args("--frobnicate"), 7).is_err());
+}
+
+#[test]
+fn packs_match_the_page_for_the_same_setup() {
+    let (generator, _) = load_generator(&[]).unwrap();
+    let options = parse_args(&args("--set MH1:2 --set WAR:1 --packs 3 --seed 42"), 0).unwrap();
+    let mut draft = Draft::new(generator.clone(), SeededRng::new(0));
+    draft.start_setup(&options.setup).unwrap();
+    let packs = generate_packs(&mut draft).unwrap();
+    assert_eq!(packs.len(), 3);
+    // a shared link to the same setup opens the same first pack
+    let (shared, _) = setup_from_query("#sets=MH1:2,WAR:1&seed=42&packs=3&picks=2").unwrap();
+    let mut page = Draft::new(generator.clone(), SeededRng::new(1));
+    page.start_setup(&shared).unwrap();
+    assert_eq!(page.current_pack().cards, packs[0].contents);
+    let too_many = parse_args(&args("--set MH1:4 --packs 3"), 0).unwrap();
+    let mut draft = Draft::new(generator.clone(), SeededRng::new(0));
+    assert_eq!(draft.start_setup(&too_many.setup), Err(DraftError::TooManyPacks{assigned:4, num_packs:3}));
+    let unknown = parse_args(&args("--set XYZ:1"), 0).unwrap();
+    assert_eq!(draft.start_setup(&unknown.setup), Err(DraftError::UnknownSet(String::from("XYZ"))));
+}
+
+#[test]
+fn terminal_draft_takes_names_and_numbers() {
+    let (generator, _) = load_generator(&[(String::from("cube.txt"), String::from("Alpha\nBravo\nCharlie\nDelta\nEcho\nFoxtrot\nGolf\nHotel\nIndia\nJuliett"))]).unwrap();
+    let options = parse_args(&args("--set cube:1 --packs 1 --size 4"), 5).unwrap();
+    let mut draft = Draft::new(generator, SeededRng::new(0));
+    draft.start_setup(&options.setup).unwrap();
+    let pack = draft.current_pack().clone();
+    // a pool too small for a pack only warns, but a card without a mana value is refused
+    assert!(load_generator(&[(String::from("tiny.txt"), String::from("Alpha\nBravo"))]).is_ok());
+    assert!(load_generator(&[(String::from("bad.json"), String::from(r#"{"Alpha":{"r":"c"}}"#))]).is_err());
+    assert!(load_generator(&[(String::from("empty.json"), String::from("{}"))]).is_err());
+    let first = pack.cards[0].name.clone();
+    assert_eq!(parse_pick(&format!("{}, 2", first.to_lowercase()), &pack, 2).unwrap(), vec![first.clone(), pack.cards[1].name.clone()]);
+    assert!(parse_pick("1, 1", &pack, 2).is_err());
+    assert!(parse_pick("9", &pack, 1).is_err());
+    assert!(parse_pick("1", &pack, 2).is_err());
+    let mut output = vec![];
+    run_draft(&mut draft, &mut "1\n1,2\n4,3\n".as_bytes(), &mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains("pick 2 cards"));
+    assert!(draft.is_finished());
+    assert!(output.contains(&format!("1 {}", first)));
+}
+
+#[test]
+fn pool_paths_name_sets_by_their_file_name() {
+    assert_eq!(pool_file_name("pools/cubes/vintage.txt"), "vintage.txt");
+    assert_eq!(pool_file_name("vintage.txt"), "vintage.txt");
+    let (_, pool_sets) = load_generator(&[(String::from(pool_file_name("pools/cubes/vintage.txt")), String::from("Alpha\nBravo"))]).unwrap();
+    assert_eq!(pool_sets.keys().collect::<Vec<_>>(), vec!["vintage"]);
+}
@@ -0,0 +1,10 @@
+//! Helpers shared by the native tests.
+
+use supreme_drafter_rs::builtin_generator;
+use supreme_drafter_rs::draft::Draft;
+use supreme_drafter_rs::rng::SeededRng;
+
+/// A draft over the bundled sets and pack templates, loaded as the page and command line load them.
+pub fn new_draft(seed:u64) -> Draft {
+    Draft::new(builtin_generator().unwrap(), SeededRng::new(seed))
+}
@@ -0,0 +1,56 @@
+//! Native tests for uploaded pools and cube lists.
+
+use supreme_drafter_rs::custom_sets::{clashing_sets, parse_cube_list, parse_pool_file};
+use supreme_drafter_rs::draft::{Draft, DraftFormat, SetGenerator};
+use supreme_drafter_rs::rng::SeededRng;
+
+#[test]
+fn cube_list_reads_names_and_rarities() {
+    let cards = parse_cube_list("# my cube\nLightning Bolt\nJace, the Mind Sculptor | m\n\n// removed\nCounterspell | uncommon\n").unwrap();
+    assert_eq!(cards.len(), 3);
+    assert_eq!(cards["Lightning Bolt"]["r"], "c");
+    assert_eq!(cards["Jace, the Mind Sculptor"]["r"], "m");
+    assert_eq!(cards["Counterspell"]["r"], "u");
+    assert!(parse_cube_list("Lightning Bolt | shiny").is_err());
+    // a mana cost or mana value keeps the cube's curve
+    let cards = parse_cube_list("Lightning Bolt | {R}\nKitchen Finks | u | {1}{G/W}{G/W}\nWalking Ballista | {X}{X}\nGhostly Flicker | 3 | c\nMemnite").unwrap();
+    assert_eq!(cards["Lightning Bolt"]["c"], 1);
+    assert_eq!(cards["Lightning Bolt"]["mc"], "{R}");
+    assert_eq!((&cards["Kitchen Finks"]["c"], &cards["Kitchen Finks"]["r"]), (&serde_json::json!(3), &serde_json::json!("u")));
+    assert_eq!(cards["Walking Ballista"]["c"], 0);
+    assert_eq!(cards["Ghostly Flicker"]["c"], 3);
+    assert_eq!(cards["Memnite"]["c"], 0);
+    assert!(parse_cube_list("# nothing here\n").is_err());
+}
+
+#[test]
+fn json_pools_are_named_after_the_file_when_holding_one_set() {
+    let single = parse_pool_file("my_cube.json", r#"{"Lightning Bolt":{"c":1,"r":"c"}}"#).unwrap();
+    assert!(single.contains_key("my_cube"));
+    let many = parse_pool_file("sets.json", r#"{"AAA":{"Bolt":{"c":1,"r":"c"}},"BBB":{"Shock":{"c":1,"r":"c"}}}"#).unwrap();
+    assert_eq!(many.keys().cloned().collect::<Vec<_>>(), vec!["AAA", "BBB"]);
+    assert!(parse_pool_file("broken.json", "{").is_err());
+    assert!(parse_pool_file("odd.json", r#"{"AAA":[1, 2]}"#).is_err());
+}
+
+#[test]
+fn cube_without_rarities_can_be_drafted() {
+    let list:String = (0..40).map(|idx| format!("Card {}\n", idx)).collect();
+    let mut draft = Draft::new(SetGenerator::new(serde_json::json!({})).unwrap(), SeededRng::new(5));
+    draft.add_sets(parse_pool_file("cube.txt", &list).unwrap());
+    draft.start(&[(String::from("cube"), 18)], DraftFormat::SUPREME).unwrap();
+    let cards = &draft.current_pack().cards;
+    assert_eq!(cards.len(), 14);
+    assert!(cards[0].img_url.contains("scryfall"));
+}
+
+#[test]
+fn uploads_may_not_take_a_built_in_set_name() {
+    let built_in = vec![String::from("MH1"), String::from("my_cube")];
+    let upload = parse_pool_file("MH1.txt", "Lightning Bolt").unwrap();
+    assert_eq!(clashing_sets(&upload, &built_in, &serde_json::Map::new()), vec!["MH1"]);
+    // uploading a custom set again replaces the earlier upload
+    let earlier = parse_pool_file("my_cube.txt", "Counterspell").unwrap();
+    let again = parse_pool_file("my_cube.txt", "Lightning Bolt").unwrap();
+    assert!(clashing_sets(&again, &built_in, &earlier).is_empty());
+}
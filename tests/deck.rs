@@ -0,0 +1,49 @@
+//! Native tests for post-draft deck building.
+
+use supreme_drafter_rs::deck::{basic_land, Deck};
+use supreme_drafter_rs::draft::{Card, DraftFormat};
+use supreme_drafter_rs::export::{export_deck, ExportFormat};
+
+mod common;
+
+fn card(name:&str, cmc:i16) -> Card {
+    Card{name:String::from(name), cmc, ..Card::default()}
+}
+
+#[test]
+fn cards_move_between_main_and_sideboard() {
+    let mut deck = Deck::from_picks(vec![card("Shock", 1), card("Shock", 1), card("Giant Growth", 1)]);
+    assert!(deck.move_to_sideboard("Shock"));
+    assert_eq!((deck.main.len(), deck.sideboard.len()), (2, 1));
+    assert!(!deck.move_to_main("Giant Growth"));
+    assert!(deck.move_to_main("Shock"));
+    assert_eq!((deck.main.len(), deck.sideboard.len()), (3, 0));
+}
+
+#[test]
+fn land_station_counts_towards_deck_size_and_export() {
+    let mut deck = Deck::from_picks(vec![card("Shock", 1), card("Forest", 0)]);
+    deck.move_to_sideboard("Shock");
+    deck.set_basic_land_count("Mountain", 2);
+    deck.set_basic_land_count("Forest", 1);
+    assert_eq!(deck.main_size(), 4);
+    deck.set_basic_land_count("Forest", 0);
+    assert_eq!(deck.basic_land_count("Forest"), 0);
+    // the Forest opened in a pack stays a pick
+    assert_eq!(deck.main_size(), 3);
+    assert!(basic_land("Mountain").is_land());
+    assert_eq!(export_deck(ExportFormat::TextByName, "", &deck.main_with_lands(), &deck.sideboard), "1 Forest\n2 Mountain\n\n1 Shock\n");
+}
+
+#[test]
+fn finished_draft_starts_with_every_pick_in_main() {
+    let mut draft = common::new_draft(4);
+    draft.start(&[(String::from("MH1"), 2)], DraftFormat{num_packs:2, ..DraftFormat::SUPREME}).unwrap();
+    while !draft.is_finished() {
+        let names:Vec<String> = draft.current_pack().cards.iter().take(draft.picks_required()).map(|card| card.name.clone()).collect();
+        draft.pick(&names).unwrap();
+    }
+    let deck = Deck::from_picks(draft.picks().to_vec());
+    assert_eq!(deck.main_size(), 4);
+    assert_eq!(deck.main, draft.picks());
+}
@@ -0,0 +1,250 @@
+//! Native tests for the draft engine.
+
+use supreme_drafter_rs::builtin_generator;
+use supreme_drafter_rs::draft::{Draft, DraftFormat, SetGenerator, ShortSlot};
+use supreme_drafter_rs::error::DraftError;
+use supreme_drafter_rs::rng::SeededRng;
+mod common;
+
+use common::new_draft;
+
+fn pack_names(draft:&Draft) -> Vec<String> {
+    draft.current_pack().cards.iter().map(|card| card.name.clone()).collect()
+}
+
+#[test]
+fn packs_have_no_duplicate_non_basics() {
+    let mut draft = new_draft(42);
+    draft.start(&[(String::from("MH1"), 18)], DraftFormat::SUPREME).unwrap();
+    let cards = &draft.current_pack().cards;
+    assert!(cards.len() >= 14);
+    for (idx, card) in cards.iter().enumerate().take(14) {
+        assert!(!cards[idx+1..14].contains(card), "{} appears twice", card.name);
+    }
+}
+
+#[test]
+fn full_draft_collects_every_pick() {
+    let mut draft = new_draft(42);
+    draft.start(&[(String::from("MH1"), 10), (String::from("WAR"), 4)], DraftFormat::SUPREME).unwrap();
+    assert_eq!(draft.generator().pack_series.len(), DraftFormat::SUPREME.num_packs as usize);
+    while !draft.is_finished() {
+        let names:Vec<String> = draft.current_pack().cards.iter()
+            .take(draft.picks_required())
+            .map(|card| card.name.clone())
+            .collect();
+        draft.pick(&names).unwrap();
+    }
+    assert_eq!(draft.picks().len(), DraftFormat::SUPREME.total_picks() as usize);
+    assert!(draft.current_pack().cards.is_empty());
+}
+
+#[test]
+fn picking_unknown_card_does_nothing() {
+    let mut draft = new_draft(42);
+    draft.start(&[(String::from("MH1"), 18)], DraftFormat::SUPREME).unwrap();
+    let pack_size = draft.current_pack().cards.len();
+    draft.pick(&[String::from("Not A Card")]).unwrap();
+    assert_eq!(draft.current_pack().cards.len(), pack_size);
+    assert_eq!(draft.current_pack().num_picks, 0);
+}
+
+#[test]
+fn same_seed_replays_same_draft() {
+    let setup = [(String::from("MH1"), 6), (String::from("WAR"), 6)];
+    let mut first = new_draft(7);
+    let mut second = new_draft(7);
+    first.start(&setup, DraftFormat::SUPREME).unwrap();
+    second.start(&setup, DraftFormat::SUPREME).unwrap();
+    assert_eq!(first.generator().pack_series, second.generator().pack_series);
+    while !first.is_finished() {
+        let names = pack_names(&first);
+        assert_eq!(names, pack_names(&second));
+        let num_picks = first.picks_required();
+        first.pick(&names[..num_picks]).unwrap();
+        second.pick(&names[..num_picks]).unwrap();
+    }
+}
+
+#[test]
+fn reseeding_restarts_identically() {
+    let setup = [(String::from("MH1"), 18)];
+    let mut draft = new_draft(3);
+    draft.start(&setup, DraftFormat::SUPREME).unwrap();
+    let opening = pack_names(&draft);
+    draft.set_rng(SeededRng::new(3));
+    draft.start(&setup, DraftFormat::SUPREME).unwrap();
+    assert_eq!(opening, pack_names(&draft));
+}
+
+#[test]
+fn saved_draft_resumes_where_it_left_off() {
+    let mut draft = new_draft(11);
+    draft.start(&[(String::from("MH1"), 9), (String::from("WAR"), 9)], DraftFormat::SUPREME).unwrap();
+    for _ in 0..3 {
+        let names = pack_names(&draft);
+        draft.pick(&names[..draft.picks_required()]).unwrap();
+    }
+    let json = serde_json::to_string(&draft).unwrap();
+    let mut resumed = new_draft(0);
+    resumed.resume(serde_json::from_str(&json).unwrap());
+    assert_eq!(resumed.pack_number(), draft.pack_number());
+    assert_eq!(pack_names(&resumed), pack_names(&draft));
+    while !draft.is_finished() {
+        let names = pack_names(&draft);
+        let num_picks = draft.picks_required();
+        draft.pick(&names[..num_picks]).unwrap();
+        resumed.pick(&names[..num_picks]).unwrap();
+        assert_eq!(pack_names(&resumed), pack_names(&draft));
+    }
+}
+
+// One rare, three uncommons and ten commons: exactly enough for a pack.
+fn minimal_set(rare:serde_json::Value) -> serde_json::Value {
+    let mut set = serde_json::Map::new();
+    set.insert(String::from("Rare"), rare);
+    for idx in 0..3 {
+        set.insert(format!("Uncommon {}", idx), serde_json::json!({"i":idx, "c":2, "r":"u"}));
+    }
+    for idx in 0..10 {
+        set.insert(format!("Common {}", idx), serde_json::json!({"i":100 + idx, "c":1, "r":"c"}));
+    }
+    serde_json::json!({"TST":set})
+}
+
+fn opened_rare(rare:serde_json::Value) -> supreme_drafter_rs::draft::Card {
+    let mut draft = Draft::new(SetGenerator::new(minimal_set(rare)).unwrap(), SeededRng::new(1));
+    draft.start(&[(String::from("TST"), 18)], DraftFormat::SUPREME).unwrap();
+    draft.current_pack().cards.iter().find(|card| card.name == "Rare").unwrap().clone()
+}
+
+#[test]
+fn optional_card_fields_are_parsed() {
+    let card = opened_rare(serde_json::json!({
+        "i":1, "c":3, "r":"r", "ci":"UG", "mc":"{1}{G}{U}", "t":"Creature — Elf Wizard", "p":"2", "th":"3", "o":"Flying"
+    }));
+    assert_eq!(card.colors, "UG");
+    assert_eq!(card.mana_cost.as_deref(), Some("{1}{G}{U}"));
+    assert!(card.is_creature());
+    assert!(!card.is_land());
+    assert_eq!((card.power.as_deref(), card.toughness.as_deref()), (Some("2"), Some("3")));
+    assert_eq!(card.text.as_deref(), Some("Flying"));
+}
+
+#[test]
+fn colors_fall_back_to_mana_cost_and_old_data_still_loads() {
+    let card = opened_rare(serde_json::json!({"i":1, "c":2, "r":"r", "mc":"{R}{W}"}));
+    assert_eq!(card.colors, "WR");
+    let card = opened_rare(serde_json::json!({"i":1, "c":2, "r":"r"}));
+    assert_eq!(card.colors, "");
+    assert_eq!(card.type_line, None);
+}
+
+#[test]
+fn format_sets_pack_count_picks_and_pack_size() {
+    let format = DraftFormat{num_packs:4, picks_per_pack:3, pack_size:Some(9), seats:1};
+    let mut draft = new_draft(21);
+    draft.start(&[(String::from("MH1"), 2)], format).unwrap();
+    assert_eq!(draft.generator().pack_series.len(), 4);
+    let mut num_picks = 0;
+    while !draft.is_finished() {
+        assert_eq!(draft.current_pack().cards.len(), 9);
+        assert_eq!(draft.picks_required(), 3);
+        let names = pack_names(&draft);
+        draft.pick(&names[..3]).unwrap();
+        num_picks += 3;
+    }
+    assert_eq!(num_picks, format.total_picks());
+}
+
+#[test]
+fn pack_advances_when_it_runs_out_before_the_picks_do() {
+    let mut draft = new_draft(22);
+    draft.start(&[(String::from("MH1"), 2)], DraftFormat{num_packs:2, picks_per_pack:5, pack_size:Some(3), seats:1}).unwrap();
+    assert_eq!(draft.picks_required(), 3);
+    let names = pack_names(&draft);
+    draft.pick(&names).unwrap();
+    assert_eq!(draft.pack_number(), 1);
+}
+
+#[test]
+fn picks_keep_pick_order_and_old_saves_still_load() {
+    let mut draft = new_draft(8);
+    draft.start(&[(String::from("MH1"), 18)], DraftFormat::SUPREME).unwrap();
+    let mut picked = vec![];
+    for _ in 0..3 {
+        let names = pack_names(&draft);
+        picked.extend_from_slice(&names[..draft.picks_required()]);
+        draft.pick(&names[..draft.picks_required()]).unwrap();
+    }
+    let names:Vec<String> = draft.picks().iter().map(|card| card.name.clone()).collect();
+    assert_eq!(names, picked);
+    // saves from before pick order was kept hold CMC columns instead
+    let mut json = serde_json::to_value(&draft).unwrap();
+    let picks = json.as_object_mut().unwrap().remove("picks").unwrap();
+    json["sorted_picks"] = serde_json::json!([picks]);
+    let mut resumed = new_draft(0);
+    resumed.resume(serde_json::from_value(json).unwrap());
+    assert_eq!(resumed.picks().len(), 6);
+}
+
+#[test]
+fn bad_set_data_is_an_error_instead_of_a_panic() {
+    assert!(matches!(SetGenerator::new(serde_json::json!([])), Err(DraftError::BadData(_))));
+    let mut draft = Draft::new(SetGenerator::new(minimal_set(serde_json::json!({"i":1, "r":"r"}))).unwrap(), SeededRng::new(1));
+    match draft.start(&[(String::from("TST"), 18)], DraftFormat::SUPREME) {
+        Err(DraftError::InvalidSet(issue)) => assert_eq!(issue.card.as_deref(), Some("Rare")),
+        other => panic!("expected an invalid set, got {:?}", other)
+    }
+    assert_eq!(draft.start(&[], DraftFormat::SUPREME), Err(DraftError::NoSets));
+    assert_eq!(draft.start(&[(String::from("XYZ"), 1)], DraftFormat::SUPREME), Err(DraftError::UnknownSet(String::from("XYZ"))));
+}
+
+#[test]
+fn formats_and_setups_without_packs_are_errors_instead_of_panics() {
+    let mut draft = new_draft(1);
+    let setup = [(String::from("MH1"), 0)];
+    let no_packs = DraftFormat{num_packs:0, ..DraftFormat::SUPREME};
+    assert!(matches!(draft.start(&setup, no_packs), Err(DraftError::BadFormat(_))));
+    let no_picks = DraftFormat{picks_per_pack:0, ..DraftFormat::SUPREME};
+    assert!(matches!(draft.start(&setup, no_picks), Err(DraftError::BadFormat(_))));
+    let empty_packs = DraftFormat{pack_size:Some(0), ..DraftFormat::SUPREME};
+    assert!(matches!(draft.start(&setup, empty_packs), Err(DraftError::BadFormat(_))));
+    let mut generator = builtin_generator().unwrap();
+    assert_eq!(generator.init_from_setup_packs(&setup, 0, &mut SeededRng::new(1)), Err(DraftError::NoPacks));
+}
+
+#[test]
+fn empty_pools_are_errors_instead_of_hanging() {
+    let special:serde_json::Map<_, _> = (0..20).map(|idx| (format!("Special {}", idx), serde_json::json!({"c":1, "r":"s"}))).collect();
+    let mut draft = Draft::new(SetGenerator::new(serde_json::json!({"EMPTY":{}, "SPECIAL":special})).unwrap(), SeededRng::new(1));
+    for set in &["EMPTY", "SPECIAL"] {
+        match draft.start(&[(String::from(*set), 18)], DraftFormat::SUPREME) {
+            Err(DraftError::InvalidSet(issue)) => assert_eq!(issue.set, *set),
+            other => panic!("expected {} to be refused, got {:?}", set, other)
+        }
+    }
+}
+
+#[test]
+fn small_pools_borrow_then_repeat_instead_of_hanging() {
+    let mut set = serde_json::Map::new();
+    set.insert(String::from("Rare"), serde_json::json!({"c":4, "r":"r"}));
+    for idx in 0..5 {
+        set.insert(format!("Uncommon {}", idx), serde_json::json!({"c":2, "r":"u"}));
+    }
+    for idx in 0..6 {
+        set.insert(format!("Common {}", idx), serde_json::json!({"c":1, "r":"c"}));
+    }
+    let mut draft = Draft::new(SetGenerator::new(serde_json::json!({"TST":set})).unwrap(), SeededRng::new(3));
+    draft.start(&[(String::from("TST"), 18)], DraftFormat::SUPREME).unwrap();
+    let pack = draft.current_pack();
+    assert_eq!(pack.cards.len(), 14);
+    let mut names = pack_names(&draft);
+    names.sort();
+    names.dedup();
+    // every card of the set once, then two commons again
+    assert_eq!(names.len(), 12);
+    assert_eq!(pack.short_slots, vec![ShortSlot{slot:String::from("common"), borrowed:2, repeated:2}]);
+    assert_eq!(pack.short_slots[0].to_string(), "the common slot ran out of cards (2 from another rarity, 2 repeated)");
+}
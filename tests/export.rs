@@ -0,0 +1,74 @@
+//! Native tests for decklist exports, checked against fixtures in `tests/fixtures/export`.
+//!
+//! The fixtures are written by hand after files saved by each client, not
+//! from this crate's output; the MTGO catalog ids in them are made up.
+
+use supreme_drafter_rs::draft::Card;
+use supreme_drafter_rs::export::{export_deck, export_file_name, ExportFormat};
+
+fn card(name:&str, cmc:i16, set:&str) -> Card {
+    Card{name:String::from(name), cmc, set:String::from(set), ..Card::default()}
+}
+
+fn mtgo_card(name:&str, cmc:i16, set:&str, mtgo_id:i64) -> Card {
+    Card{mtgo_id:Some(mtgo_id), ..card(name, cmc, set)}
+}
+
+fn fixture_deck() -> (Vec<Card>, Vec<Card>) {
+    // one Lightning Bolt was opened from another set, so it is a different printing
+    let main = vec![
+        mtgo_card("Lightning Bolt", 1, "M10", 1001),
+        card("Jace, the Mind Sculptor", 4, "WWK"),
+        mtgo_card("Lightning Bolt", 1, "2XM", 2001),
+        mtgo_card("Counterspell", 2, "7ED", 3001),
+        mtgo_card("Lightning Bolt", 1, "M10", 1001),
+        mtgo_card("Chandra's Outrage", 4, "M10", 1002)
+    ];
+    (main, vec![mtgo_card("Pyroblast", 1, "ICE", 4001)])
+}
+
+fn export(format:ExportFormat) -> String {
+    let (main, sideboard) = fixture_deck();
+    export_deck(format, "M10 & friends", &main, &sideboard)
+}
+
+#[test]
+fn arena_matches_fixture() {
+    assert_eq!(export(ExportFormat::Arena), include_str!("fixtures/export/arena.txt"));
+}
+
+#[test]
+fn mtgo_matches_fixture() {
+    assert_eq!(export(ExportFormat::Mtgo), include_str!("fixtures/export/deck.dek"));
+}
+
+#[test]
+fn cockatrice_matches_fixture() {
+    assert_eq!(export(ExportFormat::Cockatrice), include_str!("fixtures/export/deck.cod"));
+}
+
+#[test]
+fn forge_matches_fixture() {
+    assert_eq!(export(ExportFormat::Forge), include_str!("fixtures/export/deck.dck"));
+}
+
+#[test]
+fn plain_text_matches_fixtures() {
+    assert_eq!(export(ExportFormat::TextByCmc), include_str!("fixtures/export/by_cmc.txt"));
+    assert_eq!(export(ExportFormat::TextByName), include_str!("fixtures/export/by_name.txt"));
+}
+
+#[test]
+fn formats_round_trip_through_codes() {
+    for format in ExportFormat::ALL.iter() {
+        assert_eq!(ExportFormat::from_code(format.code()), Some(*format));
+    }
+    // no sideboard means no empty sideboard section
+    assert_eq!(export_deck(ExportFormat::Arena, "", &[card("Shock", 1, "")], &[]), "Deck\n1 Shock\n");
+}
+
+#[test]
+fn file_names_come_from_sets_and_date() {
+    assert_eq!(export_file_name(&["MH1", "WAR"], "2020-09-01", ExportFormat::Mtgo), "MH1-WAR-draft-2020-09-01.dek");
+    assert_eq!(export_file_name(&["my cube/v2"], "", ExportFormat::Arena), "mycubev2-draft.txt");
+}
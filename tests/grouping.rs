@@ -0,0 +1,52 @@
+//! Native tests for grouped views of the pool.
+
+use supreme_drafter_rs::draft::Card;
+use supreme_drafter_rs::grouping::{group_cards, Grouping};
+
+fn card(name:&str, cmc:i16, colors:&str, type_line:&str, rarity:char, set:&str) -> Card {
+    Card{name:String::from(name), cmc, colors:String::from(colors), type_line:Some(String::from(type_line)), rarity, set:String::from(set), ..Card::default()}
+}
+
+fn pool() -> Vec<Card> {
+    vec![
+        card("Zombie", 2, "B", "Creature — Zombie", 'c', "WAR"),
+        card("Sol Ring", 1, "", "Artifact", 'u', "MH1"),
+        card("Absorb", 3, "WU", "Instant", 'r', "MH1"),
+        card("Angel", 5, "W", "Creature — Angel", 'm', "WAR"),
+        card("Bolt", 1, "R", "Instant", 'c', "MH1")
+    ]
+}
+
+fn labels_and_names(cards:&[Card], grouping:Grouping) -> Vec<(String, Vec<String>)> {
+    group_cards(cards, grouping).into_iter()
+        .map(|(label, column)| (label, column.iter().map(|card| card.name.clone()).collect()))
+        .collect()
+}
+
+fn labels(cards:&[Card], grouping:Grouping) -> Vec<String> {
+    labels_and_names(cards, grouping).into_iter().map(|(label, _)| label).collect()
+}
+
+#[test]
+fn views_group_without_touching_pick_order() {
+    let pool = pool();
+    assert_eq!(labels_and_names(&pool, Grouping::Cmc)[0], (String::from("1"), vec![String::from("Bolt"), String::from("Sol Ring")]));
+    assert_eq!(labels(&pool, Grouping::Cmc), vec!["1", "2", "3", "5"]);
+    assert_eq!(labels(&pool, Grouping::Color), vec!["W", "B", "R", "Multicolor", "Colorless"]);
+    assert_eq!(labels(&pool, Grouping::Type), vec!["Creature", "Instant", "Artifact"]);
+    assert_eq!(labels(&pool, Grouping::Rarity), vec!["Mythic", "Rare", "Uncommon", "Common"]);
+    assert_eq!(labels(&pool, Grouping::Set), vec!["WAR", "MH1"]);
+    let in_order = labels_and_names(&pool, Grouping::PickOrder);
+    assert_eq!(in_order.len(), 1);
+    assert_eq!(in_order[0].1, pool.iter().map(|card| card.name.clone()).collect::<Vec<_>>());
+    assert_eq!(pool[0].name, "Zombie");
+}
+
+#[test]
+fn pick_order_splits_into_columns() {
+    let pool:Vec<Card> = (0..25).map(|idx| card(&format!("Card {}", idx), 1, "", "Instant", 'c', "MH1")).collect();
+    assert_eq!(labels(&pool, Grouping::PickOrder), vec!["Picks 1–10", "Picks 11–20", "Picks 21–25"]);
+    for grouping in Grouping::ALL.iter() {
+        assert_eq!(Grouping::from_code(grouping.code()), Some(*grouping));
+    }
+}
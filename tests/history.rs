@@ -0,0 +1,52 @@
+//! Native tests for the pick history.
+
+use supreme_drafter_rs::draft::{Draft, DraftFormat, SetupInfo};
+use supreme_drafter_rs::history::history_to_json;
+use supreme_drafter_rs::undo::DEFAULT_UNDO_LIMIT;
+
+mod common;
+
+fn finished_draft(sets:&[(String, i16)], format:DraftFormat) -> Draft {
+    let mut draft = common::new_draft(6);
+    draft.set_clock(|| 1_600_000_000_000.);
+    draft.start(sets, format).unwrap();
+    while !draft.is_finished() {
+        let names:Vec<String> = draft.current_pack().cards.iter().rev().take(draft.picks_required()).map(|card| card.name.clone()).collect();
+        draft.pick(&names).unwrap();
+    }
+    draft
+}
+
+#[test]
+fn every_round_is_recorded() {
+    let sets = vec![(String::from("MH1"), 2), (String::from("WAR"), 1)];
+    let format = DraftFormat{num_packs:3, ..DraftFormat::SUPREME};
+    let draft = finished_draft(&sets, format);
+    let history = draft.history();
+    assert_eq!(history.len(), 3);
+    for (round, record) in history.iter().enumerate() {
+        assert_eq!(record.pack_number, round as i16);
+        assert_eq!(record.set, draft.generator().pack_series[round]);
+        assert_eq!(record.picked.len(), 2);
+        assert!(record.picked.iter().all(|name| record.contents.iter().any(|card| &card.name == name)));
+        assert_eq!(record.timestamp, Some(1_600_000_000_000.));
+    }
+    let picked:Vec<String> = history.iter().flat_map(|record| record.picked.clone()).collect();
+    let picks:Vec<String> = draft.picks().iter().map(|card| card.name.clone()).collect();
+    assert_eq!(picked, picks);
+}
+
+#[test]
+fn pod_rounds_record_each_pass_and_export_as_json() {
+    let sets = vec![(String::from("MH1"), 1)];
+    let format = DraftFormat{num_packs:1, ..DraftFormat::CLASSIC};
+    let draft = finished_draft(&sets, format);
+    let history = draft.history();
+    // one record per pick, each pack a card smaller than the last
+    assert_eq!(history.len(), history[0].contents.len());
+    assert!(history.windows(2).all(|pair| pair[1].contents.len() + 1 == pair[0].contents.len()));
+    let setup = SetupInfo{sets, seed:6, format, bot_strategies:vec![], undo_limit:DEFAULT_UNDO_LIMIT, pick_timer:None};
+    let json:serde_json::Value = serde_json::from_str(&history_to_json(&setup, history)).unwrap();
+    assert_eq!(json["setup"]["seed"], 6);
+    assert_eq!(json["picks"].as_array().unwrap().len(), history.len());
+}
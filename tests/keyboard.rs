@@ -0,0 +1,32 @@
+//! Native tests for keyboard picking.
+
+use supreme_drafter_rs::keyboard::{key_action, move_focus, Direction, KeyAction};
+
+#[test]
+fn keys_map_to_actions() {
+    assert_eq!(key_action("ArrowUp"), Some(KeyAction::Move(Direction::Up)));
+    assert_eq!(key_action(" "), Some(KeyAction::Toggle));
+    assert_eq!(key_action("Enter"), Some(KeyAction::Toggle));
+    assert_eq!(key_action("C"), Some(KeyAction::Confirm));
+    assert_eq!(key_action("1"), Some(KeyAction::ToggleIndex(0)));
+    assert_eq!(key_action("0"), Some(KeyAction::ToggleIndex(9)));
+    assert_eq!(key_action("?"), Some(KeyAction::ToggleHelp));
+    assert_eq!(key_action("x"), None);
+    assert_eq!(key_action("10"), None);
+}
+
+#[test]
+fn focus_moves_within_the_grid() {
+    // 7 cards, 3 to a row: 0 1 2 / 3 4 5 / 6
+    assert_eq!(move_focus(None, 7, 3, Direction::Down), Some(0));
+    assert_eq!(move_focus(Some(0), 7, 3, Direction::Left), Some(0));
+    assert_eq!(move_focus(Some(2), 7, 3, Direction::Right), Some(2));
+    assert_eq!(move_focus(Some(2), 7, 3, Direction::Down), Some(5));
+    assert_eq!(move_focus(Some(5), 7, 3, Direction::Down), Some(6));
+    assert_eq!(move_focus(Some(6), 7, 3, Direction::Down), Some(6));
+    assert_eq!(move_focus(Some(6), 7, 3, Direction::Right), Some(6));
+    assert_eq!(move_focus(Some(4), 7, 3, Direction::Up), Some(1));
+    // the pack shrank since the last move
+    assert_eq!(move_focus(Some(9), 7, 3, Direction::Up), Some(3));
+    assert_eq!(move_focus(Some(0), 0, 3, Direction::Right), None);
+}
@@ -0,0 +1,63 @@
+//! Native tests for pod drafting with bots.
+
+use supreme_drafter_rs::draft::{Draft, DraftFormat};
+use supreme_drafter_rs::pod::PassDirection;
+
+mod common;
+
+fn pod_draft(seed:u64) -> Draft {
+    let mut draft = common::new_draft(seed);
+    draft.start(&[(String::from("MH1"), 3)], DraftFormat::CLASSIC).unwrap();
+    draft
+}
+
+fn pick_first(draft:&mut Draft) {
+    let name = draft.current_pack().cards[0].name.clone();
+    draft.pick(&[name]).unwrap();
+}
+
+#[test]
+fn every_seat_drafts_every_card() {
+    let mut draft = pod_draft(1);
+    assert_eq!(draft.bots().len(), 7);
+    let pack_size = draft.current_pack().cards.len();
+    let mut num_picks = 0;
+    while !draft.is_finished() {
+        pick_first(&mut draft);
+        num_picks += 1;
+    }
+    assert_eq!(num_picks, pack_size * 3);
+    for bot in draft.bots() {
+        assert_eq!(bot.picks.len(), pack_size * 3);
+        assert!(bot.pack.cards.is_empty());
+    }
+}
+
+#[test]
+fn packs_pass_and_wheel_back() {
+    let mut draft = pod_draft(2);
+    assert_eq!(draft.pass_direction(), PassDirection::Left);
+    let first_pack:Vec<String> = draft.current_pack().cards.iter().map(|card| card.name.clone()).collect();
+    pick_first(&mut draft);
+    // the pack now in front of the first bot is the one the human just passed
+    let passed:Vec<String> = draft.bots()[0].pack.cards.iter().map(|card| card.name.clone()).collect();
+    assert!(passed.iter().all(|name| first_pack.contains(name)));
+    assert!(draft.wheeled_cards().is_empty());
+    for _ in 1..8 {
+        pick_first(&mut draft);
+    }
+    let wheeled = draft.wheeled_cards();
+    assert_eq!(wheeled.len(), draft.current_pack().cards.len());
+    assert!(wheeled.iter().all(|card| first_pack.contains(&card.name)));
+}
+
+#[test]
+fn direction_alternates_by_round() {
+    let mut draft = pod_draft(3);
+    let pack_size = draft.current_pack().cards.len();
+    for _ in 0..pack_size {
+        pick_first(&mut draft);
+    }
+    assert_eq!(draft.pack_number(), 1);
+    assert_eq!(draft.pass_direction(), PassDirection::Right);
+}
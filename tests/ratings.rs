@@ -0,0 +1,48 @@
+//! Native tests for importing card ratings.
+
+use supreme_drafter_rs::draft::Card;
+use supreme_drafter_rs::ratings::{parse_ratings_csv, parse_ratings_file, SetRatings};
+
+#[test]
+fn seventeen_lands_style_csv_is_read() {
+    let csv = "Name,Color,Rarity,# Seen,GIH WR\n\
+        \"Jace, the Mind Sculptor\",U,M,120,61.2%\n\
+        Lightning Bolt,R,C,900,58.0%\n\
+        Obscure Card,G,U,3,\n";
+    let ratings = parse_ratings_file("mh1.csv", csv).unwrap();
+    assert_eq!(ratings.get("Jace, the Mind Sculptor"), Some(61.2));
+    assert_eq!(ratings.get("Lightning Bolt"), Some(58.0));
+    // blank scores and cards not in the table are simply unrated
+    assert_eq!(ratings.get("Obscure Card"), None);
+    assert_eq!(ratings.get("Counterspell"), None);
+    // without a known score column, the first numeric one is used
+    let ratings = parse_ratings_csv("card,tier\nBolt,4.5\n").unwrap();
+    assert_eq!(ratings.get("Bolt"), Some(4.5));
+    assert!(parse_ratings_csv("Color,Score\nR,1\n").is_err());
+}
+
+#[test]
+fn json_ratings_accept_numbers_and_strings() {
+    let ratings = parse_ratings_file("war.json", r#"{"Bolt": 4.5, "Shock": "55%", "Odd": [1]}"#).unwrap();
+    assert_eq!(ratings.len(), 2);
+    assert_eq!(ratings.get("Shock"), Some(55.0));
+    assert!(parse_ratings_file("empty.json", "{}").is_err());
+    assert!(parse_ratings_file("broken.json", "{").is_err());
+}
+
+#[test]
+fn cards_are_rated_by_their_own_set() {
+    let mut ratings = SetRatings::default();
+    ratings.insert(String::from("AAA"), parse_ratings_file("a.json", r#"{"B": 1, "C": 3, "Shared": 5}"#).unwrap());
+    ratings.insert(String::from("BBB"), parse_ratings_file("b.json", r#"{"Shared": 0.5}"#).unwrap());
+    let card = |name:&str, set:&str| Card{name:String::from(name), set:String::from(set), ..Card::default()};
+    // a name in both tables keeps each set's score
+    assert_eq!(ratings.get(&card("Shared", "AAA")), Some(5.));
+    assert_eq!(ratings.get(&card("Shared", "BBB")), Some(0.5));
+    assert_eq!(ratings.get(&card("B", "BBB")), None);
+    let cards = [card("A", "AAA"), card("B", "AAA"), card("C", "AAA"), card("Shared", "BBB"), card("D", "AAA")];
+    let mut shown:Vec<&Card> = cards.iter().collect();
+    ratings.sort_cards(&mut shown);
+    let names:Vec<&str> = shown.iter().map(|card| card.name.as_str()).collect();
+    assert_eq!(names, vec!["C", "B", "Shared", "A", "D"]);
+}
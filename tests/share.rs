@@ -0,0 +1,70 @@
+//! Native tests for shared draft links.
+
+use supreme_drafter_rs::draft::{DraftFormat, SetupInfo};
+use supreme_drafter_rs::share::{setup_from_query, setup_to_query};
+use supreme_drafter_rs::strategy::StrategyKind;
+use supreme_drafter_rs::undo::DEFAULT_UNDO_LIMIT;
+
+#[test]
+fn setup_round_trips_through_query() {
+    let setup = SetupInfo{sets:vec![(String::from("MH1"), 10), (String::from("WAR"), 8)], seed:42, format:DraftFormat::SUPREME, bot_strategies:vec![], undo_limit:DEFAULT_UNDO_LIMIT, pick_timer:None};
+    let query = setup_to_query(&setup, true);
+    assert_eq!(query, "sets=MH1:10,WAR:8&seed=42&packs=18&picks=2&start=1");
+    assert_eq!(setup_from_query(&format!("#{}", query)), Some((setup.clone(), true)));
+    assert_eq!(setup_from_query(&setup_to_query(&setup, false)), Some((setup, false)));
+}
+
+#[test]
+fn malformed_queries_are_rejected() {
+    assert_eq!(setup_from_query(""), None);
+    assert_eq!(setup_from_query("#seed=42"), None);
+    assert_eq!(setup_from_query("#sets=MH1:10"), None);
+    assert_eq!(setup_from_query("#sets=MH1:ten&seed=42"), None);
+    assert_eq!(setup_from_query("#sets=MH1:10&seed=-1"), None);
+    assert_eq!(setup_from_query("#sets=MH1:10&seed=1&picks=0"), None);
+}
+
+#[test]
+fn format_defaults_for_older_links_and_round_trips() {
+    let (setup, _) = setup_from_query("#sets=MH1:10&seed=42").unwrap();
+    assert_eq!(setup.format, DraftFormat::SUPREME);
+    let setup = SetupInfo{sets:vec![(String::from("MH1"), 3)], seed:1, format:DraftFormat{num_packs:3, picks_per_pack:1, pack_size:Some(15), seats:1}, bot_strategies:vec![], undo_limit:DEFAULT_UNDO_LIMIT, pick_timer:None};
+    assert_eq!(setup_from_query(&setup_to_query(&setup, false)), Some((setup, false)));
+}
+
+#[test]
+fn bot_strategies_round_trip() {
+    let (setup, _) = setup_from_query("#sets=MH1:3&seed=1&packs=3&picks=1&seats=3&bots=random,rated").unwrap();
+    assert_eq!(setup.bot_strategies, vec![StrategyKind::Random, StrategyKind::HighestRated]);
+    assert_eq!(setup_from_query(&setup_to_query(&setup, false)), Some((setup, false)));
+    assert_eq!(setup_from_query("#sets=MH1:3&seed=1&bots=genius"), None);
+}
+
+#[test]
+fn undo_setting_round_trips() {
+    let (setup, _) = setup_from_query("#sets=MH1:18&seed=1&undo=0").unwrap();
+    assert_eq!(setup.undo_limit, 0);
+    assert!(setup_to_query(&setup, false).ends_with("&undo=0"));
+    let (setup, _) = setup_from_query("#sets=MH1:18&seed=1").unwrap();
+    assert_eq!(setup.undo_limit, DEFAULT_UNDO_LIMIT);
+    assert!(!setup_to_query(&setup, false).contains("undo"));
+}
+
+#[test]
+fn pick_timer_round_trips() {
+    let (setup, _) = setup_from_query("#sets=MH1:18&seed=1&timer=45").unwrap();
+    assert_eq!(setup.pick_timer, Some(45));
+    assert_eq!(setup_from_query(&setup_to_query(&setup, false)).unwrap().0, setup);
+    assert_eq!(setup_from_query("#sets=MH1:18&seed=1").unwrap().0.pick_timer, None);
+    assert!(setup_from_query("#sets=MH1:18&seed=1&timer=0").is_none());
+}
+
+#[test]
+fn custom_set_names_are_encoded() {
+    let (mut setup, _) = setup_from_query("#sets=MH1:18&seed=1").unwrap();
+    setup.sets = vec![(String::from("Tom's cube: v2, A&B"), 10), (String::from("MH1"), 8)];
+    let query = setup_to_query(&setup, false);
+    assert!(query.starts_with("sets=Tom%27s%20cube%3A%20v2%2C%20A%26B:10,MH1:8&"));
+    assert_eq!(setup_from_query(&query).unwrap().0, setup);
+    assert!(setup_from_query("#sets=bad%2:1&seed=1").is_none());
+}
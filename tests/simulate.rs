@@ -0,0 +1,35 @@
+//! Native tests for the pack statistics simulator.
+
+use supreme_drafter_rs::builtin_generator;
+use supreme_drafter_rs::rng::SeededRng;
+use supreme_drafter_rs::simulate::{intended_mythic_rate, simulate_packs, stats_to_text};
+use supreme_drafter_rs::template::PackTemplate;
+
+#[test]
+fn rates_follow_the_template() {
+    let mut generator = builtin_generator().unwrap();
+    let stats = simulate_packs(&mut generator, &[(String::from("MH1"), 1)], 2000, None, &mut SeededRng::new(8)).unwrap();
+    let mh1 = &stats[0];
+    assert_eq!(mh1.packs, 2000);
+    assert_eq!(mh1.intended_mythic_rate, intended_mythic_rate(&PackTemplate::default()));
+    assert!((mh1.mythic_rate - 0.125).abs() < 0.03, "{}", mh1.mythic_rate);
+    assert_eq!((mh1.duplicate_rate, mh1.basic_fill, mh1.average_size, mh1.short_rate), (0., 1., 15., 0.));
+    let total:f64 = mh1.card_rates.iter().map(|(_, rate)| rate).sum();
+    assert!((total - 15.).abs() < 1e-9);
+    assert!(stats_to_text(&stats, 3).contains("mythics per pack"));
+    // W16 has too few commons for a pack, so every one borrows cards but still fills its 14 slots
+    let w16 = &simulate_packs(&mut generator, &[(String::from("W16"), 1)], 50, None, &mut SeededRng::new(8)).unwrap()[0];
+    assert_eq!((w16.short_rate, w16.average_size), (1., 14.));
+}
+
+#[test]
+fn switching_sets_keeps_pools_apart() {
+    let mut generator = builtin_generator().unwrap();
+    let stats = simulate_packs(&mut generator, &[(String::from("MH1"), 1), (String::from("WAR"), 1)], 200, None, &mut SeededRng::new(2)).unwrap();
+    assert_eq!(stats.len(), 2);
+    for set in &stats {
+        assert_eq!(set.foreign_cards, 0, "{} packs held other sets' cards", set.set);
+    }
+    let mythic_pools:Vec<usize> = stats.iter().map(|set| set.pool_sizes[&'m']).collect();
+    assert_eq!(mythic_pools, vec![15, 18]);
+}
@@ -0,0 +1,47 @@
+//! Native tests for pool statistics.
+
+use supreme_drafter_rs::draft::Card;
+use supreme_drafter_rs::stats::{card_pips, PoolStats};
+
+fn card(name:&str, cmc:i16, mana_cost:Option<&str>, type_line:&str) -> Card {
+    Card{name:String::from(name), cmc, mana_cost:mana_cost.map(String::from), type_line:Some(String::from(type_line)), ..Card::default()}
+}
+
+#[test]
+fn curve_pips_and_types_are_counted() {
+    let cards = vec![
+        card("Llanowar Elves", 1, Some("{G}"), "Creature — Elf Druid"),
+        card("Kitchen Finks", 3, Some("{1}{G/W}{G/W}"), "Creature — Ouphe"),
+        card("Counterspell", 2, Some("{U}{U}"), "Instant"),
+        card("Breeding Pool", 0, None, "Land — Forest Island")
+    ];
+    let stats = PoolStats::from_cards(&cards);
+    assert_eq!(stats.curve, vec![0, 1, 1, 1]);
+    assert_eq!(stats.pips, [2, 2, 0, 0, 3]);
+    assert_eq!((stats.creatures, stats.non_creatures, stats.lands), (2, 1, 1));
+    // cards without a cost fall back to their colors
+    assert_eq!(card_pips(&Card{colors:String::from("BR"), ..Card::default()}), [0, 0, 1, 1, 0]);
+}
+
+#[test]
+fn cards_without_type_lines_are_not_guessed() {
+    // shaped like the bundled sets, which give no `t`
+    let cards = vec![
+        Card{name:String::from("Forest"), ..Card::default()},
+        Card{name:String::from("Snow-Covered Island"), ..Card::default()},
+        Card{name:String::from("Llanowar Elves"), cmc:1, colors:String::from("G"), ..Card::default()},
+        Card{name:String::from("Counterspell"), cmc:2, colors:String::from("U"), ..Card::default()}
+    ];
+    let stats = PoolStats::from_cards(&cards);
+    assert_eq!((stats.creatures, stats.non_creatures, stats.lands, stats.unknown), (0, 0, 2, 2));
+    assert_eq!(stats.curve, vec![0, 1, 1]);
+}
+
+#[test]
+fn land_split_follows_pips_and_adds_up() {
+    let stats = PoolStats{pips:[0, 5, 0, 0, 10], ..PoolStats::default()};
+    assert_eq!(stats.suggested_lands(17), vec![('U', 6), ('G', 11)]);
+    let stats = PoolStats{pips:[1, 1, 1, 0, 0], ..PoolStats::default()};
+    assert_eq!(stats.suggested_lands(17).iter().map(|(_, lands)| lands).sum::<usize>(), 17);
+    assert!(PoolStats::default().suggested_lands(17).is_empty());
+}
@@ -0,0 +1,72 @@
+//! Native tests for bot pick strategies.
+
+use std::collections::BTreeMap;
+
+use supreme_drafter_rs::draft::{Card, DraftFormat, Pack};
+use supreme_drafter_rs::ratings::{Ratings, SetRatings};
+use supreme_drafter_rs::rng::SeededRng;
+use supreme_drafter_rs::strategy::{complete_selection, rarity_rank, ColorCommitted, HighestRated, PickContext, PickStrategy, RarityFirst, StrategyKind};
+
+mod common;
+
+fn card(name:&str, rarity:char, colors:&str) -> Card {
+    Card{name:String::from(name), rarity, colors:String::from(colors), set:String::from("TST"), ..Card::default()}
+}
+
+fn choose(strategy:&dyn PickStrategy, pack:&Pack, picks:&[Card], num_picks:usize, ratings:&SetRatings) -> Vec<String> {
+    let context = PickContext{num_picks, pack_number:0, num_packs:3, ratings};
+    strategy.choose(pack, picks, &context, &mut SeededRng::new(3))
+}
+
+#[test]
+fn rarity_and_ratings_order_picks() {
+    let pack = Pack{cards:vec![card("Common", 'c', "G"), card("Mythic", 'm', "R"), card("Uncommon", 'u', "W"), card("Rare", 'r', "B")], ..Pack::default()};
+    let no_ratings = SetRatings::default();
+    assert_eq!(choose(&RarityFirst, &pack, &[], 2, &no_ratings), vec!["Mythic", "Rare"]);
+    // without a table, rated picks fall back to rarity
+    assert_eq!(choose(&HighestRated, &pack, &[], 1, &no_ratings), vec!["Mythic"]);
+    let table = Ratings::new(BTreeMap::from([(String::from("Common"), 4.5), (String::from("Uncommon"), 3.0)]));
+    let ratings = SetRatings::new(BTreeMap::from([(String::from("TST"), table)]));
+    assert_eq!(choose(&HighestRated, &pack, &[], 3, &ratings), vec!["Common", "Uncommon", "Mythic"]);
+}
+
+#[test]
+fn expired_timer_keeps_the_selection_and_fills_the_rest() {
+    let pack = Pack{cards:vec![card("Common", 'c', "G"), card("Mythic", 'm', "R"), card("Rare", 'r', "B")], ..Pack::default()};
+    let ratings = SetRatings::default();
+    let context = PickContext{num_picks:2, pack_number:0, num_packs:3, ratings:&ratings};
+    let selected = vec![String::from("Common")];
+    assert_eq!(complete_selection(&HighestRated, &pack, &[], &selected, &context, &mut SeededRng::new(3)), vec!["Common", "Mythic"]);
+    assert_eq!(complete_selection(&HighestRated, &pack, &[], &[], &context, &mut SeededRng::new(3)), vec!["Mythic", "Rare"]);
+}
+
+#[test]
+fn color_committed_stays_in_its_colors() {
+    let picks:Vec<Card> = ["W", "W", "WU", "U", "B"].iter().enumerate().map(|(idx, colors)| card(&format!("Pick {}", idx), 'c', colors)).collect();
+    let pack = Pack{cards:vec![card("Off color rare", 'r', "R"), card("Gold common", 'c', "WU"), card("Artifact", 'c', "")], ..Pack::default()};
+    let chosen = choose(&ColorCommitted::default(), &pack, &picks, 2, &SetRatings::default());
+    assert_eq!(chosen.len(), 2);
+    assert!(!chosen.contains(&String::from("Off color rare")));
+    // before committing it takes the best card regardless of color
+    assert_eq!(choose(&ColorCommitted::default(), &pack, &picks[..2], 1, &SetRatings::default()), vec!["Off color rare"]);
+}
+
+#[test]
+fn strategies_compare_in_a_headless_pod() {
+    let mut draft = common::new_draft(11);
+    draft.start(&[(String::from("MH1"), 3)], DraftFormat::CLASSIC).unwrap();
+    draft.set_bot_strategies(&[StrategyKind::Random, StrategyKind::RarityFirst]);
+    let human = StrategyKind::HighestRated.strategy();
+    while !draft.is_finished() {
+        draft.pick_with(human.as_ref()).unwrap();
+    }
+    let bots = draft.bots();
+    assert_eq!(bots[0].strategy, StrategyKind::Random);
+    assert_eq!(bots[1].strategy, StrategyKind::RarityFirst);
+    assert_eq!(bots[2].strategy, StrategyKind::default());
+    let rarity_total = |picks:&[Card]| picks.iter().map(rarity_rank).sum::<f64>();
+    assert_eq!(bots[0].picks.len(), bots[1].picks.len());
+    assert!(rarity_total(&bots[1].picks) > rarity_total(&bots[0].picks));
+    let codes:Vec<Option<StrategyKind>> = StrategyKind::ALL.iter().map(|kind| StrategyKind::from_code(kind.code())).collect();
+    assert!(codes.iter().all(Option::is_some));
+}
@@ -0,0 +1,55 @@
+//! Native tests for pack collation templates.
+
+use std::collections::BTreeMap;
+
+use supreme_drafter_rs::draft::{Draft, DraftFormat, SetGenerator};
+use supreme_drafter_rs::rng::SeededRng;
+use supreme_drafter_rs::template::{parse_templates, PackTemplate};
+use supreme_drafter_rs::{PACK_TEMPLATES_STR, SET_JSON_STR};
+
+fn opening_pack(set:&str, templates:BTreeMap<String, PackTemplate>, sets:serde_json::Value) -> Vec<String> {
+    let mut generator = SetGenerator::new(sets).unwrap();
+    generator.set_templates(templates);
+    let mut draft = Draft::new(generator, SeededRng::new(9));
+    draft.start(&[(String::from(set), 18)], DraftFormat::SUPREME).unwrap();
+    draft.current_pack().cards.iter().map(|card| card.name.clone()).collect()
+}
+
+#[test]
+fn shipped_templates_parse_and_override_collation() {
+    let templates = parse_templates(PACK_TEMPLATES_STR).unwrap();
+    assert_eq!(templates["2XM"].pack_size(), 14);
+    let sets:serde_json::Value = serde_json::from_str(SET_JSON_STR).unwrap();
+    assert_eq!(opening_pack("2XM", templates.clone(), sets.clone()).len(), 14);
+    // Masters sets trade the basic land for a foil of any rarity
+    assert_eq!(templates["A25"].slots.last().map(|slot| (slot.name.as_str(), slot.duplicates)), Some(("foil", true)));
+    assert_eq!(opening_pack("A25", templates.clone(), sets.clone()).len(), 15);
+    // MH1 keeps the usual 1/3/10, with only snow basics in its land slot
+    let mh1 = opening_pack("MH1", templates.clone(), sets.clone());
+    assert_eq!(mh1.len(), PackTemplate::default().pack_size() as usize);
+    assert!(mh1.last().unwrap().starts_with("Snow-Covered "));
+    // Unglued packs are ten cards
+    assert_eq!(opening_pack("UGL", templates, sets).len(), 10);
+}
+
+#[test]
+fn slot_filters_and_counts_are_honored() {
+    let templates = parse_templates(r#"{"TST": {"slots": [
+        {"name": "featured", "count": 2, "rarities": {"r": 1}, "filter": {"names": ["Featured A", "Featured B"]}},
+        {"name": "filler", "count": 5, "rarities": {"c": 1}, "filter": {"exclude_type": "Land"}}
+    ]}}"#).unwrap();
+    let mut cards = serde_json::Map::new();
+    for name in ["Featured A", "Featured B", "Other Rare"] {
+        cards.insert(String::from(name), serde_json::json!({"c":3, "r":"r"}));
+    }
+    for idx in 0..8 {
+        cards.insert(format!("Common {}", idx), serde_json::json!({"c":1, "r":"c"}));
+        cards.insert(format!("Land {}", idx), serde_json::json!({"c":0, "r":"c", "t":"Land"}));
+    }
+    let pack = opening_pack("TST", templates, serde_json::json!({"TST": cards}));
+    assert_eq!(pack.len(), 7);
+    let mut featured = pack[..2].to_vec();
+    featured.sort();
+    assert_eq!(featured, vec!["Featured A", "Featured B"]);
+    assert!(pack[2..].iter().all(|name| name.starts_with("Common")));
+}
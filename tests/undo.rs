@@ -0,0 +1,52 @@
+//! Native tests for undoing picks.
+
+use supreme_drafter_rs::draft::{Draft, DraftFormat};
+use supreme_drafter_rs::undo::UndoStack;
+
+mod common;
+
+fn pack_names(draft:&Draft) -> Vec<String> {
+    draft.current_pack().cards.iter().map(|card| card.name.clone()).collect()
+}
+
+#[test]
+fn stack_keeps_the_most_recent_states() {
+    let mut stack = UndoStack::new(2);
+    for state in 1..=3 {
+        stack.push(state);
+    }
+    assert_eq!(stack.len(), 2);
+    assert_eq!(stack.pop(), Some(3));
+    assert_eq!(stack.pop(), Some(2));
+    assert_eq!(stack.pop(), None);
+    let mut disabled = UndoStack::new(0);
+    disabled.push(1);
+    assert!(!disabled.is_enabled());
+    assert!(disabled.is_empty());
+}
+
+#[test]
+fn undo_restores_pack_picks_and_set_pools() {
+    let mut draft = common::new_draft(21);
+    // switching sets between packs re-prepares the pools, which undo has to bring back too
+    draft.start(&[(String::from("MH1"), 1), (String::from("WAR"), 2)], DraftFormat{num_packs:3, ..DraftFormat::SUPREME}).unwrap();
+    let mut stack = UndoStack::new(5);
+    let first = pack_names(&draft);
+    stack.push(draft.snapshot());
+    draft.pick(&first[..2]).unwrap();
+    let second = pack_names(&draft);
+    stack.push(draft.snapshot());
+    draft.pick(&second[..2]).unwrap();
+    let third = pack_names(&draft);
+    draft.resume(stack.pop().unwrap());
+    assert_eq!(draft.pack_number(), 1);
+    assert_eq!(pack_names(&draft), second);
+    assert_eq!(draft.picks().len(), 2);
+    assert_eq!(draft.history().len(), 1);
+    // the same pick again gives the same next pack, since the random state came back as well
+    draft.pick(&second[..2]).unwrap();
+    assert_eq!(pack_names(&draft), third);
+    draft.resume(stack.pop().unwrap());
+    assert_eq!(pack_names(&draft), first);
+    assert!(draft.picks().is_empty());
+}
@@ -0,0 +1,62 @@
+//! Native tests for set data validation.
+
+use std::collections::BTreeMap;
+
+use serde_json::json;
+use supreme_drafter_rs::builtin_generator;
+use supreme_drafter_rs::validate::{validate_sets, IssueKind, Severity};
+
+fn commons(count:usize) -> serde_json::Map<String, serde_json::Value> {
+    (0..count).map(|idx| (format!("Common {}", idx), json!({"c":1, "r":"c"}))).collect()
+}
+
+#[test]
+fn card_problems_are_reported_per_card() {
+    let mut cards = commons(10);
+    cards.insert(String::from("Uncommon"), json!({"c":2, "r":"u", "i":7}));
+    cards.insert(String::from("No Cost"), json!({"r":"u"}));
+    cards.insert(String::from("Half Cost"), json!({"c":2.5, "r":"u", "i":7}));
+    cards.insert(String::from("Special"), json!({"c":3, "r":"s"}));
+    let sets:serde_json::Map<_, _> = vec![(String::from("TST"), serde_json::Value::Object(cards))].into_iter().collect();
+    let report = validate_sets(&sets, &BTreeMap::new());
+    assert_eq!((report.sets, report.cards), (1, 14));
+    let kinds:Vec<(Option<&str>, &IssueKind)> = report.issues.iter().map(|issue| (issue.card.as_deref(), &issue.kind)).collect();
+    assert!(kinds.contains(&(Some("No Cost"), &IssueKind::MissingField("c"))));
+    assert!(kinds.contains(&(Some("Half Cost"), &IssueKind::NonIntegerCmc(json!(2.5)))));
+    assert!(kinds.contains(&(Some("Special"), &IssueKind::UnknownRarity(String::from("s")))));
+    assert!(kinds.iter().any(|(card, kind)| *card == Some("Uncommon") && matches!(kind, IssueKind::DuplicateMultiverseId{id:7, ..})));
+    // a set without rares or basics can still fill its rare slot from the uncommons
+    assert!(!kinds.iter().any(|(_, kind)| matches!(kind, IssueKind::EmptySlot{..})));
+    assert_eq!(report.invalid_sets(), vec!["TST"]);
+    assert!(report.to_text().ends_with("1 sets, 14 cards: 2 errors, 2 warnings"));
+}
+
+#[test]
+fn pools_too_small_for_their_slots_are_warnings() {
+    let sets:serde_json::Map<_, _> = vec![
+        (String::from("SMALL"), serde_json::Value::Object(commons(6))),
+        (String::from("BROKEN"), json!(["not", "a", "set"]))
+    ].into_iter().collect();
+    let report = validate_sets(&sets, &BTreeMap::new());
+    let issues:Vec<(&str, Severity, &IssueKind)> = report.issues.iter().map(|issue| (issue.set.as_str(), issue.severity, &issue.kind)).collect();
+    assert!(issues.contains(&("SMALL", Severity::Warning, &IssueKind::PoolTooSmall{slot:String::from("common"), needed:10, available:6})));
+    assert!(issues.contains(&("BROKEN", Severity::Error, &IssueKind::NotASet)));
+    assert_eq!(report.invalid_sets(), vec!["BROKEN"]);
+    // the small supplemental sets only get warnings, so every bundled set can be drafted
+    let bundled = builtin_generator().unwrap().validate();
+    assert!(!bundled.has_errors());
+}
+
+#[test]
+fn sets_whose_packs_would_be_empty_are_errors() {
+    let special:serde_json::Map<_, _> = vec![(String::from("Special"), json!({"c":3, "r":"s"})), (String::from("Forest"), json!({"c":0, "r":"c"}))].into_iter().collect();
+    let sets:serde_json::Map<_, _> = vec![
+        (String::from("EMPTY"), json!({})),
+        (String::from("SPECIAL"), serde_json::Value::Object(special))
+    ].into_iter().collect();
+    let report = validate_sets(&sets, &BTreeMap::new());
+    for set in &["EMPTY", "SPECIAL"] {
+        assert!(report.issues.iter().any(|issue| issue.set == *set && issue.severity == Severity::Error && issue.kind == IssueKind::NoFillableSlots));
+    }
+    assert_eq!(report.invalid_sets(), vec!["EMPTY", "SPECIAL"]);
+}